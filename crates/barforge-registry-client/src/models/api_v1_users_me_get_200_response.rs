@@ -37,6 +37,8 @@ pub struct ApiV1UsersMeGet200Response {
     pub discord_url: Option<String>,
     #[serde(rename = "sponsor_url", skip_serializing_if = "Option::is_none")]
     pub sponsor_url: Option<String>,
+    #[serde(rename = "signing_key", skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
     #[serde(rename = "verified_author")]
     pub verified_author: bool,
     #[serde(rename = "role")]
@@ -70,6 +72,7 @@ impl ApiV1UsersMeGet200Response {
             bluesky_url: None,
             discord_url: None,
             sponsor_url: None,
+            signing_key: None,
             verified_author,
             role,
             module_count,
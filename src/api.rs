@@ -106,6 +106,7 @@ pub fn map_author_profile(
         bio: profile.bio,
         website_url: profile.website_url,
         verified_author: profile.verified_author,
+        signing_key: profile.signing_key,
         module_count: parse_u64(profile.module_count, "module count")?,
         created_at: profile.created_at,
     };
@@ -351,6 +352,7 @@ mod tests {
             bluesky_url: None,
             discord_url: None,
             sponsor_url: None,
+            signing_key: None,
             verified_author: true,
             role: api_models::UserRole::User,
             module_count: 2,
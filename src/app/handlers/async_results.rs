@@ -2,9 +2,12 @@ use iced::Task;
 
 use crate::app::message::Message;
 use crate::app::state::{
-    App, AuthorLoadingState, LoadingState, NotificationKind, ReviewsLoadingState,
+    App, AuthorLoadingState, ConfirmationAction, DiffLoadingState, LoadingState, NotificationKind,
+    PendingInstall, ReviewsLoadingState, Screen,
 };
 use crate::domain::{AuthorProfile, InstalledModule, ModuleUuid, RegistryIndex, ReviewsResponse};
+use crate::services::ModuleDiff;
+use crate::tasks::{self, UninstallError};
 
 pub fn handle_registry_loaded(
     app: &mut App,
@@ -58,6 +61,11 @@ pub fn handle_installed_loaded(
         Ok(modules) => {
             app.installed_uuids = modules.iter().map(|m| m.uuid.to_string()).collect();
             app.installed_modules = modules;
+            return Task::batch([
+                tasks::check_installed_dependencies(&app.installed_modules),
+                tasks::verify_installed_integrity(&app.installed_modules),
+                tasks::sweep_revocations(&app.installed_modules, app.revocation.max_list_age_hours),
+            ]);
         }
         Err(e) => {
             app.push_notification(
@@ -74,14 +82,20 @@ pub fn handle_install_completed(
     result: Result<InstalledModule, String>,
 ) -> Task<Message> {
     app.module_detail.installing = false;
+    app.module_detail.requested_permissions = None;
     match result {
         Ok(module) => {
+            let checks = Task::batch([
+                tasks::check_installed_dependencies(std::slice::from_ref(&module)),
+                tasks::verify_installed_integrity(std::slice::from_ref(&module)),
+            ]);
             app.installed_uuids.insert(module.uuid.to_string());
             app.installed_modules.push(module);
             app.push_notification(
                 "Module installed successfully".to_string(),
                 NotificationKind::Success,
             );
+            return checks;
         }
         Err(e) => {
             app.push_notification(format!("Installation failed: {e}"), NotificationKind::Error);
@@ -90,6 +104,32 @@ pub fn handle_install_completed(
     Task::none()
 }
 
+pub fn handle_install_review_required(app: &mut App, pending: PendingInstall) -> Task<Message> {
+    app.module_detail.requested_permissions = Some(pending.permissions.clone());
+    if let Some(warning) = pending.author_key.warning(&pending.author) {
+        app.push_notification(warning, NotificationKind::Warning);
+    }
+    app.confirmation.pending_action = Some(ConfirmationAction::ReviewInstall(pending));
+    Task::none()
+}
+
+pub fn handle_install_cancelled(app: &mut App, result: Result<String, String>) -> Task<Message> {
+    app.module_detail.installing = false;
+    app.module_detail.requested_permissions = None;
+    match result {
+        Ok(_) => {
+            app.push_notification("Installation cancelled".to_string(), NotificationKind::Info);
+        }
+        Err(e) => {
+            app.push_notification(
+                format!("Installation cancelled, but cleanup failed: {e}"),
+                NotificationKind::Error,
+            );
+        }
+    }
+    Task::none()
+}
+
 pub fn handle_toggle_completed(
     app: &mut App,
     result: Result<String, (String, String)>,
@@ -115,18 +155,42 @@ pub fn handle_toggle_completed(
 
 pub fn handle_uninstall_completed(
     app: &mut App,
-    result: Result<String, (String, String)>,
+    result: Result<(String, Option<String>), (String, UninstallError)>,
 ) -> Task<Message> {
     match result {
-        Ok(uuid) => {
+        Ok((uuid, script_output)) => {
             app.installed.uninstalling.remove(&uuid);
             app.installed_uuids.remove(&uuid);
+            app.installed.dep_reports.remove(&uuid);
+            app.installed.revoked.remove(&uuid);
+            crate::tray::set_revoked_count(app.installed.revoked.len());
             app.installed_modules.retain(|m| m.uuid.to_string() != uuid);
             app.push_notification("Module uninstalled".to_string(), NotificationKind::Success);
+            if let Some(summary) = script_output
+                .as_deref()
+                .and_then(|out| out.lines().rev().find(|l| !l.trim().is_empty()))
+            {
+                app.push_notification(
+                    format!("Uninstall script: {}", summary.trim()),
+                    NotificationKind::Info,
+                );
+            }
         }
         Err((uuid, e)) => {
             app.installed.uninstalling.remove(&uuid);
             app.push_notification(format!("Uninstall failed: {e}"), NotificationKind::Error);
+            if let UninstallError::Script(e) = e
+                && let Some(module) = app
+                    .installed_modules
+                    .iter()
+                    .find(|m| m.uuid.to_string() == uuid)
+            {
+                app.confirmation.pending_action = Some(ConfirmationAction::ForceUninstallModule {
+                    uuid,
+                    name: module.waybar_module_name.clone(),
+                    error: e,
+                });
+            }
         }
     }
     Task::none()
@@ -144,6 +208,20 @@ pub fn handle_author_loaded(app: &mut App, result: Result<AuthorProfile, String>
     Task::none()
 }
 
+pub fn handle_update_diff_loaded(
+    app: &mut App,
+    result: Result<(String, ModuleDiff), (String, String)>,
+) -> Task<Message> {
+    let (uuid, loading) = match result {
+        Ok((uuid, diff)) => (uuid, DiffLoadingState::Loaded(diff)),
+        Err((uuid, e)) => (uuid, DiffLoadingState::Failed(e)),
+    };
+    if app.screen == Screen::UpdateDiff(uuid) {
+        app.update_diff.loading = loading;
+    }
+    Task::none()
+}
+
 pub fn handle_module_reviews_loaded(
     app: &mut App,
     result: Result<(ModuleUuid, ReviewsResponse), String>,
@@ -1,8 +1,12 @@
 use iced::Task;
 
 use crate::app::message::Message;
-use crate::app::state::{App, NotificationKind};
+use crate::app::state::{
+    App, AuthorKeyChange, BatchUpdateReport, ConfirmationAction, NotificationKind, PendingUpdate,
+    PermissionEscalation,
+};
 use crate::domain::InstalledModule;
+use crate::services::{LocalChangesPolicy, ORIG_SUFFIX, Resolution, ResolvedFile};
 use crate::tasks;
 
 pub fn handle_install_module(app: &mut App, uuid: crate::domain::ModuleUuid) -> Task<Message> {
@@ -19,9 +23,11 @@ pub fn handle_install_module(app: &mut App, uuid: crate::domain::ModuleUuid) ->
     if let Some(registry) = &app.registry
         && let Some(module) = registry.find_by_uuid(&uuid_str)
     {
+        app.module_detail.install_output.clear();
         return tasks::install_module(
             uuid_str,
             module.name.clone(),
+            module.author.clone(),
             module.version.clone(),
             module.repo_url.clone(),
             module.checksum.clone(),
@@ -86,22 +92,41 @@ pub fn handle_position_changed(app: &mut App, result: Result<String, String>) ->
 pub fn handle_uninstall_module(app: &mut App, uuid: crate::domain::ModuleUuid) -> Task<Message> {
     let uuid_str = uuid.to_string();
     app.installed.uninstalling.insert(uuid_str.clone());
-    tasks::uninstall_module(uuid_str)
+    tasks::uninstall_module(uuid_str, false)
+}
+
+pub fn handle_recheck_dependencies(
+    app: &mut App,
+    uuid: crate::domain::ModuleUuid,
+) -> Task<Message> {
+    let uuid_str = uuid.to_string();
+    let Some(module) = app.installed_modules.iter().find(|m| m.uuid == uuid) else {
+        return Task::none();
+    };
+    let install_path = module.install_path.clone();
+    app.installed.rechecking_deps.insert(uuid_str);
+    tasks::recheck_dependencies(uuid, install_path)
+}
+
+fn pending_update(app: &App, uuid: &str, modified: Vec<String>) -> Option<PendingUpdate> {
+    let installed = app
+        .installed_modules
+        .iter()
+        .find(|m| m.uuid.to_string() == uuid)?;
+    let registry_module = app.registry.as_ref()?.find_by_uuid(uuid)?;
+    let pending =
+        PendingUpdate::from_registry(installed.waybar_module_name.clone(), registry_module)?;
+    Some(PendingUpdate {
+        modified,
+        ..pending
+    })
 }
 
 pub fn handle_update_module(app: &mut App, uuid: crate::domain::ModuleUuid) -> Task<Message> {
     let uuid_str = uuid.to_string();
-    if app.installed_modules.iter().any(|m| m.uuid == uuid)
-        && let Some(registry) = &app.registry
-        && let Some(registry_module) = registry.find_by_uuid(&uuid_str)
-        && let Some(new_version) = &registry_module.version
-    {
+    if pending_update(app, &uuid_str, Vec::new()).is_some() {
         app.installed.updating.insert(uuid_str.clone());
-        return tasks::update_module(
-            uuid_str,
-            registry_module.repo_url.clone(),
-            new_version.clone(),
-        );
+        return tasks::check_local_changes(uuid_str);
     }
     app.push_notification(
         "Cannot update: module not found".to_string(),
@@ -110,6 +135,65 @@ pub fn handle_update_module(app: &mut App, uuid: crate::domain::ModuleUuid) -> T
     Task::none()
 }
 
+pub fn handle_local_changes_checked(
+    app: &mut App,
+    result: Result<(String, Vec<String>), (String, String)>,
+) -> Task<Message> {
+    let (uuid, modified) = match result {
+        Ok(checked) => checked,
+        Err((uuid, e)) => {
+            app.installed.updating.remove(&uuid);
+            app.push_notification(format!("Update failed: {e}"), NotificationKind::Error);
+            return Task::none();
+        }
+    };
+
+    let Some(pending) = pending_update(app, &uuid, modified) else {
+        app.installed.updating.remove(&uuid);
+        app.push_notification(
+            "Cannot update: module not found".to_string(),
+            NotificationKind::Error,
+        );
+        return Task::none();
+    };
+
+    if pending.modified.is_empty() {
+        return start_update(app, pending, LocalChangesPolicy::Merge);
+    }
+    app.confirmation.pending_action = Some(ConfirmationAction::ResolveLocalChanges(pending));
+    Task::none()
+}
+
+pub(super) fn start_update(
+    app: &App,
+    pending: PendingUpdate,
+    policy: LocalChangesPolicy,
+) -> Task<Message> {
+    let approved = app
+        .installed
+        .approved_escalations
+        .get(&pending.uuid)
+        .cloned();
+    let approved_key = app
+        .installed
+        .approved_key_changes
+        .get(&pending.uuid)
+        .cloned();
+    tasks::update_module(pending, policy, approved, approved_key)
+}
+
+pub fn handle_resolve_local_changes(app: &mut App, policy: LocalChangesPolicy) -> Task<Message> {
+    match app.confirmation.pending_action.take() {
+        Some(ConfirmationAction::ResolveLocalChanges(pending)) => {
+            start_update(app, pending, policy)
+        }
+        other => {
+            app.confirmation.pending_action = other;
+            Task::none()
+        }
+    }
+}
+
 pub fn handle_update_all_modules(app: &mut App) -> Task<Message> {
     if app.installed.updating_all {
         return Task::none();
@@ -120,21 +204,9 @@ pub fn handle_update_all_modules(app: &mut App) -> Task<Message> {
         .iter()
         .filter_map(|installed| {
             let uuid = installed.uuid.to_string();
-            app.registry.as_ref().and_then(|registry| {
-                registry
-                    .modules
-                    .iter()
-                    .find(|m| m.uuid.to_string() == uuid)
-                    .and_then(|reg_mod| {
-                        reg_mod.version.as_ref().and_then(|new_ver| {
-                            if new_ver > &installed.version {
-                                Some((uuid, reg_mod.repo_url.clone(), new_ver.clone()))
-                            } else {
-                                None
-                            }
-                        })
-                    })
-            })
+            let reg_mod = app.registry.as_ref()?.find_by_uuid(&uuid)?;
+            PendingUpdate::from_registry(installed.waybar_module_name.clone(), reg_mod)
+                .filter(|update| update.version > installed.version)
         })
         .collect();
 
@@ -152,12 +224,21 @@ pub fn handle_update_all_modules(app: &mut App) -> Task<Message> {
 
 pub fn handle_update_completed(
     app: &mut App,
-    result: Result<InstalledModule, String>,
+    result: Result<(InstalledModule, Vec<ResolvedFile>), String>,
 ) -> Task<Message> {
     match result {
-        Ok(updated_module) => {
+        Ok((updated_module, resolved)) => {
             let uuid = updated_module.uuid.to_string();
             app.installed.updating.remove(&uuid);
+            app.installed.escalations.remove(&uuid);
+            app.installed.approved_escalations.remove(&uuid);
+            app.installed.key_changes.remove(&uuid);
+            app.installed.approved_key_changes.remove(&uuid);
+            if app.installed.revoked.remove(&uuid).is_some() {
+                crate::tray::set_revoked_count(app.installed.revoked.len());
+            }
+            let integrity_check =
+                tasks::verify_installed_integrity(std::slice::from_ref(&updated_module));
 
             if let Some(existing) = app
                 .installed_modules
@@ -166,12 +247,15 @@ pub fn handle_update_completed(
             {
                 existing.version = updated_module.version;
                 existing.registry_version = updated_module.registry_version;
+                existing.approved_permissions = updated_module.approved_permissions;
             }
 
-            app.push_notification(
-                format!("Updated {}", updated_module.waybar_module_name),
-                NotificationKind::Success,
-            );
+            let name = updated_module.waybar_module_name;
+            app.push_notification(format!("Updated {name}"), NotificationKind::Success);
+            if let Some(message) = local_changes_summary(&name, &resolved) {
+                app.push_notification(message, NotificationKind::Warning);
+            }
+            return integrity_check;
         }
         Err(e) => {
             app.push_notification(format!("Update failed: {e}"), NotificationKind::Error);
@@ -180,10 +264,115 @@ pub fn handle_update_completed(
     Task::none()
 }
 
-pub fn handle_update_all_completed(app: &mut App, result: Result<usize, String>) -> Task<Message> {
+fn local_changes_summary(name: &str, resolved: &[ResolvedFile]) -> Option<String> {
+    let conflicted: Vec<&str> = resolved
+        .iter()
+        .filter(|file| file.resolution == Resolution::Conflicted)
+        .map(|file| file.path.as_str())
+        .collect();
+    if !conflicted.is_empty() {
+        return Some(format!(
+            "Merge conflicts in {name}: {} (your versions saved as {ORIG_SUFFIX})",
+            conflicted.join(", ")
+        ));
+    }
+    let saved = resolved.iter().any(|file| {
+        matches!(
+            file.resolution,
+            Resolution::Overwritten | Resolution::Merged
+        )
+    });
+    saved.then(|| format!("Local changes to {name} were saved as {ORIG_SUFFIX} files"))
+}
+
+pub fn handle_update_key_changed(app: &mut App, change: AuthorKeyChange) -> Task<Message> {
+    app.installed.updating.remove(&change.uuid);
+    app.installed.approved_key_changes.remove(&change.uuid);
+    app.push_notification(
+        format!(
+            "{} v{} is not signed with its author's usual key; review it on the Updates screen",
+            change.name, change.version
+        ),
+        NotificationKind::Warning,
+    );
+    app.installed
+        .key_changes
+        .insert(change.uuid.clone(), change);
+    Task::none()
+}
+
+pub fn handle_update_blocked(app: &mut App, escalation: PermissionEscalation) -> Task<Message> {
+    app.installed.updating.remove(&escalation.uuid);
+    app.installed.approved_escalations.remove(&escalation.uuid);
+    app.push_notification(
+        format!(
+            "{} v{} requests new access; review it on the Updates screen",
+            escalation.name, escalation.version
+        ),
+        NotificationKind::Warning,
+    );
+    app.installed
+        .escalations
+        .insert(escalation.uuid.clone(), escalation);
+    Task::none()
+}
+
+pub fn handle_update_all_completed(
+    app: &mut App,
+    result: Result<BatchUpdateReport, String>,
+) -> Task<Message> {
     app.installed.updating_all = false;
     match result {
-        Ok(count) => {
+        Ok(report) => {
+            let blocked = report.blocked;
+            if !blocked.is_empty() {
+                app.push_notification(
+                    format!(
+                        "{} update{} held back for permission review",
+                        blocked.len(),
+                        if blocked.len() == 1 { "" } else { "s" }
+                    ),
+                    NotificationKind::Warning,
+                );
+            }
+            app.installed.escalations.extend(
+                blocked
+                    .into_iter()
+                    .map(|escalation| (escalation.uuid.clone(), escalation)),
+            );
+            if !report.key_changes.is_empty() {
+                let names: Vec<&str> = report
+                    .key_changes
+                    .iter()
+                    .map(|change| change.name.as_str())
+                    .collect();
+                app.push_notification(
+                    format!("Held back for author key review: {}", names.join(", ")),
+                    NotificationKind::Warning,
+                );
+            }
+            app.installed.key_changes.extend(
+                report
+                    .key_changes
+                    .into_iter()
+                    .map(|change| (change.uuid.clone(), change)),
+            );
+            if !report.kept_local.is_empty() {
+                let modules: Vec<String> = report
+                    .kept_local
+                    .iter()
+                    .map(|(name, files)| format!("{name} ({})", files.join(", ")))
+                    .collect();
+                app.push_notification(
+                    format!(
+                        "Kept your edited files instead of the new version: {}. \
+                         Reinstall a module to take its upstream files.",
+                        modules.join("; ")
+                    ),
+                    NotificationKind::Warning,
+                );
+            }
+            let count = report.updated;
             app.push_notification(
                 format!(
                     "Updated {} module{}",
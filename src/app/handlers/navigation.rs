@@ -2,7 +2,10 @@ use iced::Task;
 use iced::widget::image;
 
 use crate::app::message::Message;
-use crate::app::state::{App, AuthorLoadingState, ReviewsLoadingState, Screen, ScreenshotState};
+use crate::app::state::{
+    App, AuthorLoadingState, DiffLoadingState, PendingUpdate, ReviewsLoadingState, Screen,
+    ScreenshotState,
+};
 use crate::domain::ModuleUuid;
 use crate::tasks;
 
@@ -70,3 +73,19 @@ pub fn handle_author_clicked(app: &mut App, username: String) -> Task<Message> {
     app.screen = Screen::AuthorProfile(username.clone());
     tasks::load_author_profile(username)
 }
+
+pub fn handle_view_update_diff(app: &mut App, uuid: ModuleUuid) -> Task<Message> {
+    let uuid = uuid.to_string();
+    let Some(update) = app
+        .registry
+        .as_ref()
+        .and_then(|r| r.find_by_uuid(&uuid))
+        .and_then(|m| PendingUpdate::from_registry(m.name.clone(), m))
+    else {
+        return Task::none();
+    };
+
+    app.update_diff.loading = DiffLoadingState::Loading;
+    app.screen = Screen::UpdateDiff(uuid);
+    tasks::load_update_diff(update)
+}
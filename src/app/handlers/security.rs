@@ -3,8 +3,11 @@ use iced::Task;
 use crate::app::message::Message;
 use crate::app::state::{App, NotificationKind};
 use crate::domain::ModuleUuid;
-use crate::security::SandboxStatus;
-use crate::services::{DepReport, InstallStage};
+use crate::security::{RevokedModule, SandboxStatus};
+use crate::services::{DepReport, InstallStage, IntegrityReport};
+use crate::tasks;
+
+const INSTALL_OUTPUT_LINES: usize = 200;
 
 pub fn handle_install_progress(
     app: &mut App,
@@ -19,21 +22,43 @@ pub fn handle_install_progress(
     Task::none()
 }
 
+pub fn handle_install_output(app: &mut App, uuid: String, line: String) -> Task<Message> {
+    if let crate::app::state::Screen::ModuleDetail(detail_uuid) = &app.screen
+        && *detail_uuid == uuid
+    {
+        let output = &mut app.module_detail.install_output;
+        if output.len() == INSTALL_OUTPUT_LINES {
+            output.remove(0);
+        }
+        output.push(line);
+    }
+    Task::none()
+}
+
 pub fn handle_dependency_check_completed(
     app: &mut App,
-    result: Result<DepReport, String>,
+    result: Result<(ModuleUuid, DepReport), (ModuleUuid, String)>,
 ) -> Task<Message> {
     match result {
-        Ok(report) => {
+        Ok((uuid, report)) => {
+            let uuid = uuid.to_string();
+            let was_rechecking = app.installed.rechecking_deps.remove(&uuid);
             if !report.all_satisfied {
                 let missing = report.missing_required.join(", ");
                 app.push_notification(
                     format!("Missing dependencies: {missing}"),
                     NotificationKind::Warning,
                 );
+            } else if was_rechecking {
+                app.push_notification(
+                    "All dependencies satisfied".to_string(),
+                    NotificationKind::Success,
+                );
             }
+            app.installed.dep_reports.insert(uuid, report);
         }
-        Err(e) => {
+        Err((uuid, e)) => {
+            app.installed.rechecking_deps.remove(&uuid.to_string());
             app.push_notification(
                 format!("Dependency check failed: {e}"),
                 NotificationKind::Error,
@@ -43,6 +68,73 @@ pub fn handle_dependency_check_completed(
     Task::none()
 }
 
+pub fn handle_dependencies_checked(
+    app: &mut App,
+    results: Vec<Result<(ModuleUuid, DepReport), (ModuleUuid, String)>>,
+) -> Task<Message> {
+    let mut missing = Vec::new();
+    let mut failed = Vec::new();
+    for result in results {
+        match result {
+            Ok((uuid, report)) => {
+                let uuid = uuid.to_string();
+                if !report.all_satisfied {
+                    missing.push(format!(
+                        "{} ({})",
+                        module_label(app, &uuid),
+                        report.missing_required.join(", ")
+                    ));
+                }
+                app.installed.dep_reports.insert(uuid, report);
+            }
+            Err((uuid, e)) => {
+                tracing::warn!("Dependency check failed for {uuid}: {e}");
+                failed.push(module_label(app, &uuid.to_string()));
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        app.push_notification(
+            format!("Missing dependencies: {}", missing.join("; ")),
+            NotificationKind::Warning,
+        );
+    }
+    if !failed.is_empty() {
+        app.push_notification(
+            format!("Dependency check failed for {}", failed.join(", ")),
+            NotificationKind::Error,
+        );
+    }
+    Task::none()
+}
+
+fn module_label(app: &App, uuid: &str) -> String {
+    app.installed_modules
+        .iter()
+        .find(|m| m.uuid.to_string() == uuid)
+        .map_or_else(|| uuid.to_string(), |m| m.waybar_module_name.clone())
+}
+
+pub fn handle_integrity_checked(
+    app: &mut App,
+    result: Result<(String, IntegrityReport), (String, String)>,
+) -> Task<Message> {
+    match result {
+        Ok((uuid, report)) => {
+            if !report.is_clean() {
+                tracing::warn!("Module {uuid} differs from its install manifest: {report}");
+            }
+            app.installed.integrity.insert(uuid, report);
+        }
+        Err((uuid, e)) => {
+            tracing::debug!("Integrity check skipped for {uuid}: {e}");
+            app.installed.integrity.remove(&uuid);
+        }
+    }
+    Task::none()
+}
+
 pub fn handle_revocation_check_completed(
     app: &mut App,
     result: Result<(), String>,
@@ -58,6 +150,101 @@ pub fn handle_revocation_check_completed(
     Task::none()
 }
 
+pub fn handle_revocation_sweep(app: &mut App) -> Task<Message> {
+    if app.installed_modules.is_empty() {
+        return Task::none();
+    }
+    tasks::sweep_revocations(&app.installed_modules, app.revocation.max_list_age_hours)
+}
+
+pub fn handle_revocation_sweep_completed(
+    app: &mut App,
+    result: Result<Vec<RevokedModule>, String>,
+) -> Task<Message> {
+    let revoked = match result {
+        Ok(revoked) => revoked,
+        Err(e) => {
+            tracing::warn!("Revocation sweep failed: {e}");
+            return Task::none();
+        }
+    };
+
+    let newly_revoked: Vec<RevokedModule> = revoked
+        .iter()
+        .filter(|r| !app.installed.revoked.contains_key(&r.uuid))
+        .cloned()
+        .collect();
+    app.installed.revoked = revoked.into_iter().map(|r| (r.uuid.clone(), r)).collect();
+    crate::tray::set_revoked_count(app.installed.revoked.len());
+
+    if newly_revoked.is_empty() {
+        return Task::none();
+    }
+
+    let names = newly_revoked
+        .iter()
+        .map(|r| format!("{} v{}", r.uuid, r.version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    tracing::warn!("Installed modules have been revoked: {names}");
+    app.push_notification(
+        format!("Revoked modules installed: {names}"),
+        NotificationKind::Error,
+    );
+
+    if !app.revocation.auto_disable_revoked {
+        crate::services::notify_desktop("Revoked Barforge modules installed", &names);
+        return Task::none();
+    }
+
+    let to_disable: Vec<String> = app
+        .installed_modules
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.uuid.to_string())
+        .filter(|uuid| newly_revoked.iter().any(|r| r.uuid == *uuid))
+        .collect();
+    if to_disable.is_empty() {
+        return Task::none();
+    }
+    app.installed.toggling.extend(to_disable.iter().cloned());
+    tasks::disable_revoked_modules(to_disable)
+}
+
+pub fn handle_revoked_modules_disabled(
+    app: &mut App,
+    results: Vec<Result<String, (String, String)>>,
+) -> Task<Message> {
+    let mut disabled = Vec::new();
+    for result in results {
+        match result {
+            Ok(uuid) => {
+                app.installed.toggling.remove(&uuid);
+                if let Some(module) = app
+                    .installed_modules
+                    .iter_mut()
+                    .find(|m| m.uuid.to_string() == uuid)
+                {
+                    module.enabled = false;
+                }
+                disabled.push(uuid);
+            }
+            Err((uuid, e)) => {
+                app.installed.toggling.remove(&uuid);
+                app.push_notification(
+                    format!("Failed to disable revoked module {uuid}: {e}"),
+                    NotificationKind::Error,
+                );
+            }
+        }
+    }
+
+    if !disabled.is_empty() {
+        crate::services::notify_desktop("Revoked Barforge modules disabled", &disabled.join(", "));
+    }
+    Task::none()
+}
+
 pub fn handle_signature_verified(app: &mut App, result: Result<(), String>) -> Task<Message> {
     if let Err(e) = result {
         app.module_detail.installing = false;
@@ -2,6 +2,7 @@ use iced::Task;
 
 use crate::app::message::Message;
 use crate::app::state::{App, NotificationKind, Screen};
+use crate::security::OfflinePolicy;
 use crate::theme::ThemeMode;
 
 pub fn handle_system_theme_changed(app: &mut App, is_dark: bool) -> Task<Message> {
@@ -39,6 +40,32 @@ pub fn handle_toggle_tray(app: &mut App, enabled: bool) -> Task<Message> {
     Task::none()
 }
 
+pub fn handle_toggle_runtime_sandbox(app: &mut App, enabled: bool) -> Task<Message> {
+    app.sandbox_runtime = enabled;
+    app.save_settings();
+    app.push_notification(
+        "Takes effect when modules are next enabled or updated".to_string(),
+        NotificationKind::Info,
+    );
+    Task::none()
+}
+
+pub fn handle_toggle_offline_installs(app: &mut App, enabled: bool) -> Task<Message> {
+    app.revocation.offline_policy = if enabled {
+        OfflinePolicy::AllowOffline
+    } else {
+        OfflinePolicy::FailClosed
+    };
+    app.save_settings();
+    Task::none()
+}
+
+pub fn handle_toggle_auto_disable_revoked(app: &mut App, enabled: bool) -> Task<Message> {
+    app.revocation.auto_disable_revoked = enabled;
+    app.save_settings();
+    Task::none()
+}
+
 pub fn handle_open_preferences(app: &mut App, uuid: crate::domain::ModuleUuid) -> Task<Message> {
     let uuid_str = uuid.to_string();
     if let Some(installed) = app.installed_modules.iter().find(|m| m.uuid == uuid) {
@@ -124,11 +151,13 @@ pub fn handle_escape_pressed(app: &mut App) -> Task<Message> {
         app.preferences.schema = None;
         app.preferences.values.clear();
     } else if app.confirmation.pending_action.is_some() {
-        app.confirmation.pending_action = None;
+        return super::handle_cancel_confirmation(app);
     } else if matches!(app.screen, Screen::ModuleDetail(_)) {
         app.screen = Screen::Browse;
         app.module_detail.screenshot = crate::app::state::ScreenshotState::NotLoaded;
         app.module_detail.installing = false;
+    } else if matches!(app.screen, Screen::UpdateDiff(_)) {
+        app.screen = Screen::Updates;
     } else if !app.browse.search_query.is_empty() && app.screen == Screen::Browse {
         app.browse.search_query.clear();
         app.browse.pending_search = None;
@@ -4,7 +4,9 @@ use iced::Task;
 
 use crate::app::message::Message;
 use crate::app::state::{App, ConfirmationAction, NotificationKind, Screen, ScreenshotState};
+use crate::domain::ModuleUuid;
 use crate::security::validate_web_url;
+use crate::services::LocalChangesPolicy;
 use crate::tasks;
 use crate::tray::TrayEvent;
 
@@ -23,6 +25,7 @@ pub fn handle_tick(app: &mut App) -> Task<Message> {
     if let Some(tray_event) = app.poll_tray_events() {
         return match tray_event {
             TrayEvent::ShowWindow => Task::done(Message::TrayShowWindow),
+            TrayEvent::ShowInstalled => Task::done(Message::Navigate(Screen::Installed)),
             TrayEvent::CheckUpdates => Task::done(Message::TrayCheckUpdates),
             TrayEvent::Quit => Task::done(Message::TrayQuit),
         };
@@ -37,7 +40,7 @@ pub fn handle_show_notification(app: &mut App, message: String, kind: Notificati
 
 pub fn handle_dismiss_notification(app: &mut App) -> Task<Message> {
     if app.confirmation.pending_action.is_some() {
-        app.confirmation.pending_action = None;
+        return handle_cancel_confirmation(app);
     } else if matches!(app.screen, Screen::ModuleDetail(_)) {
         app.screen = Screen::Browse;
         app.module_detail.screenshot = ScreenshotState::NotLoaded;
@@ -57,15 +60,52 @@ pub fn handle_confirm_action(app: &mut App) -> Task<Message> {
         match action {
             ConfirmationAction::UninstallModule { uuid, .. } => {
                 app.installed.uninstalling.insert(uuid.clone());
-                return tasks::uninstall_module(uuid);
+                return tasks::uninstall_module(uuid, false);
+            }
+            ConfirmationAction::ForceUninstallModule { uuid, .. } => {
+                app.installed.uninstalling.insert(uuid.clone());
+                return tasks::uninstall_module(uuid, true);
+            }
+            ConfirmationAction::ReviewInstall(pending) => {
+                return tasks::finish_install(pending);
+            }
+            ConfirmationAction::ResolveLocalChanges(pending) => {
+                return super::start_update(app, pending, LocalChangesPolicy::Merge);
+            }
+            ConfirmationAction::ApprovePermissions(escalation) => {
+                let Ok(uuid) = ModuleUuid::try_from(escalation.uuid.as_str()) else {
+                    return Task::none();
+                };
+                app.installed.escalations.remove(&escalation.uuid);
+                app.installed
+                    .approved_escalations
+                    .insert(escalation.uuid, escalation.requested);
+                return super::handle_update_module(app, uuid);
+            }
+            ConfirmationAction::ApproveAuthorKey(change) => {
+                let Ok(uuid) = ModuleUuid::try_from(change.uuid.as_str()) else {
+                    return Task::none();
+                };
+                app.installed.key_changes.remove(&change.uuid);
+                app.installed
+                    .approved_key_changes
+                    .insert(change.uuid, change.status);
+                return super::handle_update_module(app, uuid);
             }
         }
     }
     Task::none()
 }
 
-pub fn handle_cancel_confirmation(app: &mut App) {
-    app.confirmation.pending_action = None;
+pub fn handle_cancel_confirmation(app: &mut App) -> Task<Message> {
+    match app.confirmation.pending_action.take() {
+        Some(ConfirmationAction::ReviewInstall(pending)) => tasks::cancel_install(pending),
+        Some(ConfirmationAction::ResolveLocalChanges(pending)) => {
+            app.installed.updating.remove(&pending.uuid);
+            Task::none()
+        }
+        _ => Task::none(),
+    }
 }
 
 pub fn handle_clear_cache() -> Task<Message> {
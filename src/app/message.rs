@@ -1,13 +1,18 @@
 use iced::widget::image;
 
 use crate::app::state::{
-    CategoryFilter, ConfirmationAction, NotificationKind, Screen, SortField, ViewMode,
+    AuthorKeyChange, BatchUpdateReport, CategoryFilter, ConfirmationAction, NotificationKind,
+    PendingInstall, PermissionEscalation, Screen, SortField, ViewMode,
 };
 use crate::domain::{
     AuthorProfile, BarSection, InstalledModule, ModuleUuid, RegistryIndex, ReviewsResponse,
 };
-use crate::security::SandboxStatus;
-use crate::services::{DepReport, InstallStage, PreferenceValue};
+use crate::security::{RevokedModule, SandboxStatus};
+use crate::services::{
+    DepReport, InstallStage, IntegrityReport, LocalChangesPolicy, ModuleDiff, PreferenceValue,
+    ResolvedFile,
+};
+use crate::tasks::UninstallError;
 use crate::theme::ThemeMode;
 
 #[derive(Debug, Clone)]
@@ -36,6 +41,7 @@ pub enum Message {
     UpdateModule(ModuleUuid),
     UpdateAllModules,
     OpenPreferences(ModuleUuid),
+    RecheckDependencies(ModuleUuid),
 
     InstalledSearchChanged(String),
     ClearInstalledSearch,
@@ -45,10 +51,18 @@ pub enum Message {
     RegistryRefreshed(Result<RegistryIndex, String>),
     InstalledLoaded(Result<Vec<InstalledModule>, String>),
     InstallCompleted(Result<InstalledModule, String>),
+    InstallReviewRequired(PendingInstall),
+    InstallCancelled(Result<String, String>),
     ToggleCompleted(Result<String, (String, String)>),
-    UninstallCompleted(Result<String, (String, String)>),
-    UpdateCompleted(Result<InstalledModule, String>),
-    UpdateAllCompleted(Result<usize, String>),
+    UninstallCompleted(Result<(String, Option<String>), (String, UninstallError)>),
+    LocalChangesChecked(Result<(String, Vec<String>), (String, String)>),
+    ResolveLocalChanges(LocalChangesPolicy),
+    UpdateCompleted(Result<(InstalledModule, Vec<ResolvedFile>), String>),
+    UpdateBlocked(PermissionEscalation),
+    UpdateKeyChanged(AuthorKeyChange),
+    UpdateAllCompleted(Result<BatchUpdateReport, String>),
+    ViewUpdateDiff(ModuleUuid),
+    UpdateDiffLoaded(Result<(String, ModuleDiff), (String, String)>),
 
     ShowNotification(String, NotificationKind),
     DismissNotification,
@@ -72,6 +86,9 @@ pub enum Message {
     ResetSettings,
     SettingsResetCompleted(Result<(), String>),
     ToggleTray(bool),
+    ToggleRuntimeSandbox(bool),
+    ToggleOfflineInstalls(bool),
+    ToggleAutoDisableRevoked(bool),
 
     FocusSearch,
     EscapePressed,
@@ -88,8 +105,17 @@ pub enum Message {
         uuid: ModuleUuid,
         stage: InstallStage,
     },
-    DependencyCheckCompleted(Result<DepReport, String>),
+    InstallOutput {
+        uuid: String,
+        line: String,
+    },
+    DependencyCheckCompleted(Result<(ModuleUuid, DepReport), (ModuleUuid, String)>),
+    DependenciesChecked(Vec<Result<(ModuleUuid, DepReport), (ModuleUuid, String)>>),
+    IntegrityChecked(Result<(String, IntegrityReport), (String, String)>),
     RevocationCheckCompleted(Result<(), String>),
+    RevocationSweep,
+    RevocationSweepCompleted(Result<Vec<RevokedModule>, String>),
+    RevokedModulesDisabled(Vec<Result<String, (String, String)>>),
     SignatureVerified(Result<(), String>),
     SandboxStatusChanged(SandboxStatus),
 
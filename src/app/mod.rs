@@ -17,9 +17,9 @@ use crate::theme::{
     menu_style, pick_list_style,
 };
 use crate::widget::{
-    confirmation_dialog, empty_state, empty_state_dynamic, empty_state_with_action, module_card,
-    module_detail_screen, module_row, module_table, notification_toast, preferences_modal,
-    settings_screen, sidebar, skeleton_card,
+    ModuleFlags, confirmation_dialog, empty_state, empty_state_dynamic, empty_state_with_action,
+    module_card, module_detail_screen, module_row, module_table, notification_toast,
+    preferences_modal, settings_screen, sidebar, skeleton_card,
 };
 
 pub use message::Message;
@@ -73,6 +73,8 @@ impl App {
 
             Message::OpenPreferences(uuid) => handlers::handle_open_preferences(self, uuid),
 
+            Message::RecheckDependencies(uuid) => handlers::handle_recheck_dependencies(self, uuid),
+
             Message::InstalledSearchChanged(query) => {
                 handlers::handle_installed_search_changed(self, query)
             }
@@ -89,6 +91,12 @@ impl App {
 
             Message::InstallCompleted(result) => handlers::handle_install_completed(self, result),
 
+            Message::InstallReviewRequired(pending) => {
+                handlers::handle_install_review_required(self, pending)
+            }
+
+            Message::InstallCancelled(result) => handlers::handle_install_cancelled(self, result),
+
             Message::ToggleCompleted(result) => handlers::handle_toggle_completed(self, result),
 
             Message::UninstallCompleted(result) => {
@@ -99,8 +107,19 @@ impl App {
 
             Message::UpdateAllModules => handlers::handle_update_all_modules(self),
 
+            Message::LocalChangesChecked(result) => {
+                handlers::handle_local_changes_checked(self, result)
+            }
+            Message::ResolveLocalChanges(policy) => {
+                handlers::handle_resolve_local_changes(self, policy)
+            }
             Message::UpdateCompleted(result) => handlers::handle_update_completed(self, result),
 
+            Message::UpdateBlocked(escalation) => handlers::handle_update_blocked(self, escalation),
+            Message::UpdateKeyChanged(change) => handlers::handle_update_key_changed(self, change),
+            Message::ViewUpdateDiff(uuid) => handlers::handle_view_update_diff(self, uuid),
+            Message::UpdateDiffLoaded(result) => handlers::handle_update_diff_loaded(self, result),
+
             Message::UpdateAllCompleted(result) => {
                 handlers::handle_update_all_completed(self, result)
             }
@@ -138,10 +157,7 @@ impl App {
 
             Message::ConfirmAction => handlers::handle_confirm_action(self),
 
-            Message::CancelConfirmation => {
-                handlers::handle_cancel_confirmation(self);
-                Task::none()
-            }
+            Message::CancelConfirmation => handlers::handle_cancel_confirmation(self),
 
             Message::ClearCache => handlers::handle_clear_cache(),
 
@@ -158,6 +174,15 @@ impl App {
             }
 
             Message::ToggleTray(enabled) => handlers::handle_toggle_tray(self, enabled),
+            Message::ToggleRuntimeSandbox(enabled) => {
+                handlers::handle_toggle_runtime_sandbox(self, enabled)
+            }
+            Message::ToggleOfflineInstalls(enabled) => {
+                handlers::handle_toggle_offline_installs(self, enabled)
+            }
+            Message::ToggleAutoDisableRevoked(enabled) => {
+                handlers::handle_toggle_auto_disable_revoked(self, enabled)
+            }
 
             Message::FocusSearch => handlers::handle_focus_search(self),
 
@@ -181,14 +206,34 @@ impl App {
                 handlers::handle_install_progress(self, uuid, stage)
             }
 
+            Message::InstallOutput { uuid, line } => {
+                handlers::handle_install_output(self, uuid, line)
+            }
+
             Message::DependencyCheckCompleted(result) => {
                 handlers::handle_dependency_check_completed(self, result)
             }
 
+            Message::DependenciesChecked(results) => {
+                handlers::handle_dependencies_checked(self, results)
+            }
+
+            Message::IntegrityChecked(result) => handlers::handle_integrity_checked(self, result),
+
             Message::RevocationCheckCompleted(result) => {
                 handlers::handle_revocation_check_completed(self, result)
             }
 
+            Message::RevocationSweep => handlers::handle_revocation_sweep(self),
+
+            Message::RevocationSweepCompleted(result) => {
+                handlers::handle_revocation_sweep_completed(self, result)
+            }
+
+            Message::RevokedModulesDisabled(results) => {
+                handlers::handle_revoked_modules_disabled(self, results)
+            }
+
             Message::SignatureVerified(result) => handlers::handle_signature_verified(self, result),
 
             Message::SandboxStatusChanged(status) => {
@@ -221,6 +266,7 @@ impl App {
             Screen::Settings => self.view_settings(),
             Screen::ModuleDetail(uuid) => self.view_module_detail(uuid),
             Screen::AuthorProfile(username) => self.view_author_profile(username),
+            Screen::UpdateDiff(uuid) => self.view_update_diff(uuid),
         };
 
         let bg = self.theme.background;
@@ -284,7 +330,12 @@ impl App {
     }
 
     fn view_settings(&self) -> Element<'_, Message> {
-        settings_screen(&self.theme, self.tray_enabled)
+        settings_screen(
+            &self.theme,
+            self.tray_enabled,
+            self.sandbox_runtime,
+            self.revocation,
+        )
     }
 
     fn view_module_detail(&self, uuid: &str) -> Element<'_, Message> {
@@ -299,11 +350,9 @@ impl App {
                 .map(|m| m.installed_at);
             return module_detail_screen(
                 module,
-                &self.module_detail.screenshot,
-                &self.module_detail.reviews,
+                &self.module_detail,
                 is_installed,
                 installed_at,
-                self.module_detail.installing,
                 &self.theme,
             );
         }
@@ -424,6 +473,133 @@ impl App {
         }
     }
 
+    fn view_update_diff(&self, uuid: &str) -> Element<'_, Message> {
+        use crate::app::state::DiffLoadingState;
+
+        let theme = self.theme;
+        let name = self
+            .installed_modules
+            .iter()
+            .find(|m| m.uuid.to_string() == uuid)
+            .map_or(uuid, |m| m.waybar_module_name.as_str());
+
+        let header = container(
+            row![
+                button(text("Back").size(13.0))
+                    .padding([SPACING_XS, SPACING_SM])
+                    .on_press(Message::Navigate(Screen::Updates))
+                    .style(crate::theme::button::ghost(theme)),
+                column![
+                    text(format!("Changes in {name}"))
+                        .size(20)
+                        .color(theme.text),
+                    text("Scripts, config.jsonc and style.css in the latest release")
+                        .size(13)
+                        .color(theme.text_secondary),
+                ]
+                .spacing(SPACING_SM / 2.0),
+            ]
+            .spacing(SPACING_MD)
+            .align_y(Alignment::Center),
+        )
+        .padding([SPACING_MD, SPACING_LG]);
+
+        let content: Element<Message> = match &self.update_diff.loading {
+            DiffLoadingState::NotLoaded | DiffLoadingState::Loading => container(
+                text(format!("{} Downloading update...", self.spinner_char()))
+                    .size(16)
+                    .color(theme.text_muted),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .into(),
+            DiffLoadingState::Failed(error) => empty_state_dynamic(
+                Icon::Error,
+                "Failed to load changes",
+                error.clone(),
+                &self.theme,
+            ),
+            DiffLoadingState::Loaded(diff) if diff.is_empty() => empty_state(
+                Icon::Check,
+                "No reviewable changes",
+                "Scripts, config and style are unchanged in this update",
+                &self.theme,
+            ),
+            DiffLoadingState::Loaded(diff) => {
+                let files: Vec<Element<Message>> = diff
+                    .files
+                    .iter()
+                    .map(|file| {
+                        let summary = row![
+                            text(&file.path).size(14.0).color(theme.text),
+                            text(file.change.to_string())
+                                .size(12.0)
+                                .color(theme.text_secondary),
+                            Space::new().width(Length::Fill),
+                            text(format!("+{}", file.additions))
+                                .size(12.0)
+                                .color(theme.success),
+                            text(format!("-{}", file.deletions))
+                                .size(12.0)
+                                .color(theme.danger),
+                        ]
+                        .spacing(SPACING_SM)
+                        .align_y(Alignment::Center);
+
+                        let body: Element<Message> = match &file.patch {
+                            Some(patch) => column(patch.lines().map(|line| {
+                                let color = if line.starts_with("@@") {
+                                    theme.text_muted
+                                } else if line.starts_with('+') && !line.starts_with("+++") {
+                                    theme.success
+                                } else if line.starts_with('-') && !line.starts_with("---") {
+                                    theme.danger
+                                } else {
+                                    theme.text_secondary
+                                };
+                                text(line)
+                                    .size(12.0)
+                                    .font(iced::Font::MONOSPACE)
+                                    .color(color)
+                                    .into()
+                            }))
+                            .into(),
+                            None => text("Binary file not shown")
+                                .size(12.0)
+                                .color(theme.text_muted)
+                                .into(),
+                        };
+
+                        container(column![summary, body].spacing(SPACING_SM))
+                            .padding(SPACING_MD)
+                            .width(Length::Fill)
+                            .style(move |_| iced::widget::container::Style {
+                                background: Some(iced::Background::Color(theme.surface)),
+                                border: iced::Border {
+                                    color: theme.border,
+                                    width: 1.0,
+                                    radius: crate::theme::RADIUS_MD.into(),
+                                },
+                                ..Default::default()
+                            })
+                            .into()
+                    })
+                    .collect();
+
+                scrollable(column(files).spacing(SPACING_SM).padding(SPACING_LG))
+                    .height(Length::Fill)
+                    .into()
+            }
+        };
+
+        column![header, content]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
     fn view_browse(&self) -> Element<'_, Message> {
         let search_icon = Icon::Search.svg(16.0);
         let text_color = self.theme.text_normal;
@@ -790,7 +966,26 @@ impl App {
                 let uuid = m.uuid.to_string();
                 let is_toggling = self.installed.toggling.contains(&uuid);
                 let is_uninstalling = self.installed.uninstalling.contains(&uuid);
-                module_row(m, is_toggling, is_uninstalling, &self.theme)
+                let missing_deps = self
+                    .installed
+                    .dep_reports
+                    .get(&uuid)
+                    .map(|r| r.missing_required.as_slice())
+                    .unwrap_or_default();
+                let is_rechecking = self.installed.rechecking_deps.contains(&uuid);
+                let flags = ModuleFlags {
+                    integrity: self.installed.integrity.get(&uuid),
+                    revoked: self.installed.revoked.get(&uuid),
+                };
+                module_row(
+                    m,
+                    is_toggling,
+                    is_uninstalling,
+                    missing_deps,
+                    is_rechecking,
+                    flags,
+                    &self.theme,
+                )
             })
             .collect();
 
@@ -936,6 +1131,8 @@ impl App {
                         .map(|v| v.to_string())
                         .unwrap_or_default();
                     let is_updating = self.installed.updating.contains(&uuid_str);
+                    let escalation = self.installed.escalations.get(&uuid_str);
+                    let key_change = self.installed.key_changes.get(&uuid_str);
 
                     let theme = self.theme;
                     let primary = theme.primary;
@@ -963,6 +1160,22 @@ impl App {
                             ..Default::default()
                         })
                         .into()
+                    } else if let Some(key_change) = key_change {
+                        button(text("Review Author Key").size(12.0))
+                            .padding([SPACING_XS, SPACING_SM])
+                            .on_press(Message::RequestConfirmation(
+                                state::ConfirmationAction::ApproveAuthorKey(key_change.clone()),
+                            ))
+                            .style(crate::theme::button::danger(theme))
+                            .into()
+                    } else if let Some(escalation) = escalation {
+                        button(text("Review Access").size(12.0))
+                            .padding([SPACING_XS, SPACING_SM])
+                            .on_press(Message::RequestConfirmation(
+                                state::ConfirmationAction::ApprovePermissions(escalation.clone()),
+                            ))
+                            .style(crate::theme::button::danger(theme))
+                            .into()
                     } else {
                         button(text("Update").size(12.0))
                             .padding([SPACING_XS, SPACING_SM])
@@ -995,10 +1208,28 @@ impl App {
                                     .size(12.0)
                                     .color(theme.text_secondary),
                             ]
+                            .push(key_change.map(|_| {
+                                text("Not signed with the author's pinned key")
+                                    .size(12.0)
+                                    .color(theme.warning)
+                            }))
+                            .push(escalation.map(|escalation| {
+                                text(format!(
+                                    "Requests new access: {}",
+                                    escalation.added.join("; ")
+                                ))
+                                .size(12.0)
+                                .color(theme.warning)
+                            }))
                             .spacing(SPACING_SM / 2.0),
                             Space::new().width(Length::Fill),
+                            button(text("View Changes").size(12.0))
+                                .padding([SPACING_XS, SPACING_SM])
+                                .on_press(Message::ViewUpdateDiff(m.uuid.clone()))
+                                .style(crate::theme::button::secondary(theme)),
                             update_btn,
                         ]
+                        .spacing(SPACING_SM)
                         .align_y(Alignment::Center)
                         .padding(SPACING_MD),
                     )
@@ -1080,10 +1311,13 @@ impl App {
             keyboard_sub,
             omarchy_watcher,
             time::every(Duration::from_millis(50)).map(|_| Message::Tick),
+            time::every(REVOCATION_SWEEP_INTERVAL).map(|_| Message::RevocationSweep),
         ])
     }
 }
 
+const REVOCATION_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
 fn calculate_card_width(available_width: f32) -> f32 {
     let min_card_width = 280.0;
     let max_card_width = CARD_WIDTH;
@@ -1,16 +1,18 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use iced::widget::image;
 
 use crate::domain::{
-    AuthorProfile, InstalledModule, ModuleCategory, RegistryIndex, ReviewsResponse,
+    AuthorProfile, InstalledModule, ModuleCategory, ModuleVersion, PermissionSet, RegistryIndex,
+    RegistryModule, ReviewsResponse,
 };
-use crate::security::SandboxStatus;
+use crate::security::{AuthorKeyStatus, RevokedModule, SandboxStatus};
 use crate::services::{
-    InstallStage, ModulePreferences, OmarchyPalette, PreferencesSchema, is_omarchy_available,
-    load_omarchy_palette, load_settings,
+    DepReport, InstallStage, IntegrityReport, ModuleDiff, ModulePreferences, OmarchyPalette,
+    PreferencesSchema, ScriptFinding, is_omarchy_available, load_omarchy_palette, load_settings,
 };
 use crate::theme::{AppTheme, ThemeMode};
 
@@ -26,6 +28,7 @@ pub enum Screen {
     Settings,
     ModuleDetail(String),
     AuthorProfile(String),
+    UpdateDiff(String),
 }
 
 impl Screen {
@@ -37,6 +40,7 @@ impl Screen {
             Screen::Settings => "Settings",
             Screen::ModuleDetail(_) => "Module Detail",
             Screen::AuthorProfile(_) => "Author Profile",
+            Screen::UpdateDiff(_) => "Update Changes",
         }
     }
 
@@ -147,6 +151,14 @@ pub struct InstalledState {
     pub uninstalling: HashSet<String>,
     pub updating: HashSet<String>,
     pub updating_all: bool,
+    pub dep_reports: HashMap<String, DepReport>,
+    pub rechecking_deps: HashSet<String>,
+    pub integrity: HashMap<String, IntegrityReport>,
+    pub revoked: HashMap<String, RevokedModule>,
+    pub escalations: HashMap<String, PermissionEscalation>,
+    pub approved_escalations: HashMap<String, PermissionSet>,
+    pub key_changes: HashMap<String, AuthorKeyChange>,
+    pub approved_key_changes: HashMap<String, AuthorKeyStatus>,
     pub search_query: String,
     pub pending_search: Option<String>,
     pub search_debounce_start: Option<std::time::Instant>,
@@ -205,6 +217,8 @@ pub struct ModuleDetailState {
     pub screenshot: ScreenshotState,
     pub installing: bool,
     pub install_stage: Option<InstallStage>,
+    pub install_output: Vec<String>,
+    pub requested_permissions: Option<PermissionSet>,
     pub reviews: ReviewsLoadingState,
 }
 
@@ -222,9 +236,100 @@ pub struct AuthorProfileState {
     pub loading: AuthorLoadingState,
 }
 
+#[derive(Debug, Clone, Default)]
+pub enum DiffLoadingState {
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded(ModuleDiff),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateDiffState {
+    pub loading: DiffLoadingState,
+}
+
 #[derive(Debug, Clone)]
 pub enum ConfirmationAction {
-    UninstallModule { uuid: String, name: String },
+    UninstallModule {
+        uuid: String,
+        name: String,
+    },
+    ForceUninstallModule {
+        uuid: String,
+        name: String,
+        error: String,
+    },
+    ReviewInstall(PendingInstall),
+    ResolveLocalChanges(PendingUpdate),
+    ApprovePermissions(PermissionEscalation),
+    ApproveAuthorKey(AuthorKeyChange),
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingUpdate {
+    pub uuid: String,
+    pub name: String,
+    pub repo_url: String,
+    pub version: ModuleVersion,
+    pub author: String,
+    pub checksum: Option<String>,
+    pub modified: Vec<String>,
+}
+
+impl PendingUpdate {
+    #[must_use]
+    pub fn from_registry(name: String, module: &RegistryModule) -> Option<Self> {
+        Some(Self {
+            uuid: module.uuid.to_string(),
+            name,
+            repo_url: module.repo_url.clone(),
+            version: module.version.clone()?,
+            author: module.author.clone(),
+            checksum: module.checksum.clone(),
+            modified: Vec::new(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PermissionEscalation {
+    pub uuid: String,
+    pub name: String,
+    pub version: ModuleVersion,
+    pub requested: PermissionSet,
+    pub added: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthorKeyChange {
+    pub uuid: String,
+    pub name: String,
+    pub version: ModuleVersion,
+    pub author: String,
+    pub status: AuthorKeyStatus,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchUpdateReport {
+    pub updated: usize,
+    pub blocked: Vec<PermissionEscalation>,
+    pub key_changes: Vec<AuthorKeyChange>,
+    pub kept_local: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingInstall {
+    pub uuid: String,
+    pub name: String,
+    pub version: ModuleVersion,
+    pub staging_dir: PathBuf,
+    pub findings: Vec<ScriptFinding>,
+    pub signed_by: Option<String>,
+    pub permissions: PermissionSet,
+    pub author: String,
+    pub author_key: AuthorKeyStatus,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -260,6 +365,7 @@ pub struct App {
 
     pub module_detail: ModuleDetailState,
     pub author_profile: AuthorProfileState,
+    pub update_diff: UpdateDiffState,
     pub confirmation: ConfirmationState,
     pub preferences: PreferencesState,
 
@@ -268,6 +374,8 @@ pub struct App {
 
     pub tray_enabled: bool,
     pub tray_receiver: Option<Receiver<TrayEvent>>,
+    pub sandbox_runtime: bool,
+    pub revocation: crate::security::RevocationSettings,
 
     pub sandbox_status: Option<SandboxStatus>,
 }
@@ -323,12 +431,15 @@ impl Default for App {
             omarchy_palette,
             module_detail: ModuleDetailState::default(),
             author_profile: AuthorProfileState::default(),
+            update_diff: UpdateDiffState::default(),
             confirmation: ConfirmationState::default(),
             preferences: PreferencesState::default(),
             spinner_frame: 0,
             last_spinner_update: Instant::now(),
             tray_enabled,
             tray_receiver,
+            sandbox_runtime: settings.sandbox_runtime,
+            revocation: settings.revocation,
             sandbox_status: None,
         }
     }
@@ -502,6 +613,8 @@ impl App {
             sort_field: self.browse.sort_field,
             sort_order: self.browse.sort_order,
             tray_enabled: self.tray_enabled,
+            sandbox_runtime: self.sandbox_runtime,
+            revocation: self.revocation,
         };
         if let Err(e) = crate::services::save_settings(&settings) {
             tracing::warn!("Failed to save user settings: {e}");
@@ -1,5 +1,9 @@
+use crate::app::state::PendingUpdate;
+use crate::security::SandboxConfig;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 #[derive(Parser)]
 #[command(name = "barforge")]
@@ -15,6 +19,38 @@ pub enum Commands {
     #[command(about = "Launch the graphical interface (default)")]
     Gui,
 
+    #[command(about = "Uninstall a module, running its uninstall script first")]
+    Uninstall {
+        uuid: String,
+        #[arg(long, help = "Skip the module's uninstall script")]
+        force: bool,
+    },
+
+    #[command(about = "Run an installed module's script inside its sandbox")]
+    Run {
+        #[arg(long)]
+        interpreter: Option<String>,
+        uuid: String,
+        script: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    #[command(about = "Print the effective sandbox rules for an installed module")]
+    SandboxTest { uuid: String },
+
+    #[command(about = "Check installed modules against their install manifests")]
+    Verify { uuid: Option<String> },
+
+    #[command(about = "Check installed modules against the registry's revocation list")]
+    Audit {
+        #[arg(long, help = "Disable any revoked modules that are enabled")]
+        disable: bool,
+    },
+
+    #[command(about = "Show script, config and style changes in a module's latest release")]
+    Diff { uuid: String },
+
     #[command(hide = true)]
     InternalSandboxExec {
         #[arg(long)]
@@ -25,6 +61,210 @@ pub enum Commands {
 }
 
 impl Cli {
+    pub fn run_uninstall(uuid: String, force: bool) -> ! {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to start async runtime");
+
+        match runtime.block_on(crate::tasks::uninstall_module_async(uuid, force)) {
+            Ok((uuid, script_output)) => {
+                if let Some(output) = script_output {
+                    print!("{output}");
+                }
+                println!("Uninstalled {uuid}");
+                std::process::exit(0);
+            }
+            Err((uuid, e)) => {
+                eprintln!("Failed to uninstall {uuid}: {e}");
+                if matches!(e, crate::tasks::UninstallError::Script(_)) {
+                    eprintln!("Re-run with --force to skip the uninstall script");
+                }
+                std::process::exit(1);
+            }
+        }
+    }
+
+    pub fn run_sandbox_test(uuid: String) -> ! {
+        use crate::security::{SandboxProfile, is_sandbox_available};
+
+        let (_, config) = installed_module_sandbox(&uuid);
+
+        println!("Sandbox profile for {uuid}:");
+        println!("{}", SandboxProfile::resolve(&config));
+        println!(
+            "udp sockets: {}",
+            if config.allow_udp {
+                "allowed"
+            } else {
+                "denied"
+            }
+        );
+        println!("limits: {}", config.limits.clamped());
+        let mut env: Vec<&str> = crate::security::BASE_ENV_VARS.to_vec();
+        env.extend(["MODULE_DIR", "MODULE_DATA_DIR", "MODULE_CACHE_DIR"]);
+        env.extend(config.env.iter().map(String::as_str));
+        println!("environment: {}", env.join(", "));
+        if !is_sandbox_available() {
+            println!("WARNING: Landlock is not available; these rules would not be enforced");
+        }
+        std::process::exit(0);
+    }
+
+    pub fn run_verify(uuid: Option<String>) -> ! {
+        use crate::services::{ManifestError, paths, verify_module_integrity};
+
+        let uuids = match uuid {
+            Some(uuid) => vec![uuid],
+            None => installed_module_uuids(),
+        };
+
+        let mut failed = false;
+        for uuid in uuids {
+            let module_dir = paths::module_install_path(&uuid);
+            if !module_dir.is_dir() {
+                eprintln!("{uuid}: not installed");
+                failed = true;
+                continue;
+            }
+
+            match verify_module_integrity(&uuid, &module_dir) {
+                Ok(report) if report.is_clean() => println!("{uuid}: ok"),
+                Ok(report) => {
+                    failed = true;
+                    println!("{uuid}: {report}");
+                    let changes = [
+                        ("modified", &report.modified),
+                        ("missing", &report.missing),
+                        ("extra", &report.extra),
+                    ];
+                    for (label, files) in changes {
+                        for file in files {
+                            println!("  {label:<8} {file}");
+                        }
+                    }
+                }
+                Err(ManifestError::NotRecorded(_)) => {
+                    println!("{uuid}: no install manifest (reinstall to record one)");
+                }
+                Err(e) => {
+                    failed = true;
+                    eprintln!("{uuid}: {e}");
+                }
+            }
+        }
+
+        std::process::exit(i32::from(failed));
+    }
+
+    pub fn run_audit(disable: bool) -> ! {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to start async runtime");
+        let max_age_hours = crate::services::load_settings()
+            .revocation
+            .max_list_age_hours;
+
+        let (checked, revoked) =
+            match runtime.block_on(crate::tasks::audit_installed_async(max_age_hours)) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Revocation data unavailable: {e}");
+                    std::process::exit(2);
+                }
+            };
+
+        for module in &revoked {
+            println!(
+                "{} v{}: REVOKED ({})",
+                module.uuid, module.version, module.reason
+            );
+            if disable {
+                match runtime.block_on(crate::tasks::disable_revoked_module_async(
+                    module.uuid.clone(),
+                )) {
+                    Ok(_) => println!("  disabled"),
+                    Err((_, e)) => eprintln!("  failed to disable: {e}"),
+                }
+            }
+        }
+        println!(
+            "{checked} installed modules checked, {} revoked",
+            revoked.len()
+        );
+
+        std::process::exit(i32::from(!revoked.is_empty()));
+    }
+
+    pub fn run_diff(uuid: String) -> ! {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to start async runtime");
+
+        let registry = match runtime.block_on(crate::tasks::fetch_registry_async()) {
+            Ok(registry) => registry,
+            Err(e) => {
+                eprintln!("Failed to load registry: {e}");
+                std::process::exit(1);
+            }
+        };
+        let Some(update) = registry
+            .find_by_uuid(&uuid)
+            .and_then(|m| PendingUpdate::from_registry(m.name.clone(), m))
+        else {
+            eprintln!("No release of {uuid} found in registry");
+            std::process::exit(1);
+        };
+
+        match runtime.block_on(crate::tasks::update_diff_async(update)) {
+            Ok(diff) if diff.is_empty() => {
+                println!("No changes to scripts, config.jsonc or style.css");
+            }
+            Ok(diff) => print!("{diff}"),
+            Err(e) => {
+                eprintln!("Failed to diff update: {e}");
+                std::process::exit(1);
+            }
+        }
+        std::process::exit(0);
+    }
+
+    pub fn run_module_script(
+        uuid: String,
+        script: String,
+        args: Vec<String>,
+        interpreter: Option<String>,
+    ) -> ! {
+        if let Some(interpreter) = &interpreter
+            && !crate::services::waybar_config::is_script_interpreter(interpreter)
+        {
+            eprintln!("Unsupported interpreter: {interpreter}");
+            std::process::exit(127);
+        }
+        let (module_dir, config) = installed_module_sandbox(&uuid);
+
+        let script_path =
+            match crate::security::validate_extraction_path(&module_dir, Path::new(&script)) {
+                Ok(path) if path.is_file() => path,
+                Ok(path) => {
+                    eprintln!("Script not found: {}", path.display());
+                    std::process::exit(127);
+                }
+                Err(e) => {
+                    eprintln!("Invalid script path {script}: {e}");
+                    std::process::exit(127);
+                }
+            };
+
+        confine(&config);
+        let mut command = match &interpreter {
+            Some(interpreter) => {
+                let mut command = sandboxed_command(Path::new(interpreter), &module_dir, &config);
+                command.arg(&script_path);
+                command
+            }
+            None => sandboxed_command(&script_path, &module_dir, &config),
+        };
+        command.args(args);
+
+        let e = command.exec();
+        eprintln!("ERROR: Failed to start sandboxed script: {e}");
+        std::process::exit(127);
+    }
+
     pub fn run_sandbox_exec(script: PathBuf, module_dir: PathBuf) -> ! {
         let config_json = std::env::var("BARFORGE_SANDBOX_CONFIG")
             .expect("Missing BARFORGE_SANDBOX_CONFIG environment variable");
@@ -32,29 +272,98 @@ impl Cli {
         let config: crate::security::sandbox::SandboxConfig =
             serde_json::from_str(&config_json).expect("Invalid sandbox configuration JSON");
 
-        let result = crate::security::sandbox::apply(&config);
+        confine(&config);
+        let mut command = sandboxed_command(Path::new("bash"), &module_dir, &config);
+        command.arg(&script);
 
-        match result.status {
-            crate::security::sandbox::SandboxStatus::FullyEnforced
-            | crate::security::sandbox::SandboxStatus::PartiallyEnforced => {}
-            crate::security::sandbox::SandboxStatus::NotSupported => {
-                eprintln!("WARNING: Landlock sandbox not supported on this kernel");
-            }
-            crate::security::sandbox::SandboxStatus::Failed => {
-                eprintln!("ERROR: Failed to apply sandbox restrictions");
+        let status = match command.status() {
+            Ok(status) => status,
+            Err(e) => {
+                eprintln!("ERROR: Failed to start sandboxed script: {e}");
                 std::process::exit(127);
             }
+        };
+
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
+fn installed_module_sandbox(uuid: &str) -> (PathBuf, SandboxConfig) {
+    use crate::services::{module_sandbox_config, paths};
+
+    let module_dir = paths::module_install_path(uuid);
+    if !module_dir.is_dir() {
+        eprintln!("Module not installed: {uuid}");
+        std::process::exit(1);
+    }
+
+    match module_sandbox_config(uuid, &module_dir) {
+        Ok(config) => (module_dir, config),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
         }
+    }
+}
+
+fn installed_module_uuids() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(crate::services::paths::modules_dir()) else {
+        return Vec::new();
+    };
+    let mut uuids: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    uuids.sort();
+    uuids
+}
 
-        let status = std::process::Command::new("bash")
-            .arg(&script)
-            .current_dir(&module_dir)
-            .env("MODULE_DIR", &module_dir)
-            .status()
-            .expect("Failed to run script");
+fn confine(config: &SandboxConfig) {
+    use crate::security::sandbox::SandboxStatus;
 
-        std::process::exit(status.code().unwrap_or(1));
+    match crate::security::sandbox::apply(config).status {
+        SandboxStatus::FullyEnforced | SandboxStatus::PartiallyEnforced => {}
+        SandboxStatus::NotSupported => {
+            eprintln!("WARNING: Landlock sandbox not supported on this kernel");
+        }
+        SandboxStatus::Failed => {
+            eprintln!("ERROR: Failed to apply sandbox restrictions");
+            std::process::exit(127);
+        }
+    }
+}
+
+fn sandboxed_command(program: &Path, module_dir: &Path, config: &SandboxConfig) -> Command {
+    let limits = config.limits.clamped();
+    let running_processes = crate::security::user_process_count();
+    let filter = crate::security::SeccompFilter::new(config.allow_udp);
+    if filter.is_none() {
+        eprintln!("WARNING: seccomp filter not supported on this architecture");
+    }
+
+    let mut command = Command::new(program);
+    crate::security::scrub_environment(&mut command, module_dir, &config.env);
+    command.current_dir(module_dir);
+    if let Some(data_dir) = &config.data_dir {
+        command.env("MODULE_DATA_DIR", data_dir);
+    }
+    if let Some(cache_dir) = &config.cache_dir {
+        command.env("MODULE_CACHE_DIR", cache_dir);
+    }
+    // SAFETY: the hook only calls setrlimit and prctl, which are async-signal-safe
+    // and do not allocate; the seccomp program was built before forking.
+    unsafe {
+        command.pre_exec(move || {
+            limits.apply(running_processes)?;
+            if let Some(filter) = &filter {
+                filter.install()?;
+            }
+            Ok(())
+        });
     }
+    command
 }
 
 #[cfg(test)]
@@ -73,6 +382,88 @@ mod tests {
         assert!(matches!(cli.command, Some(Commands::Gui)));
     }
 
+    #[test]
+    fn cli_parses_uninstall_with_force() {
+        let cli = Cli::parse_from(["barforge", "uninstall", "weather@test", "--force"]);
+        match cli.command {
+            Some(Commands::Uninstall { uuid, force }) => {
+                assert_eq!(uuid, "weather@test");
+                assert!(force);
+            }
+            _ => panic!("Expected Uninstall command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_run_with_script_arguments() {
+        let cli = Cli::parse_from([
+            "barforge",
+            "run",
+            "weather@test",
+            "weather.sh",
+            "--json",
+            "-v",
+        ]);
+        match cli.command {
+            Some(Commands::Run {
+                uuid,
+                script,
+                args,
+                interpreter,
+            }) => {
+                assert!(interpreter.is_none());
+                assert_eq!(uuid, "weather@test");
+                assert_eq!(script, "weather.sh");
+                assert_eq!(args, vec!["--json", "-v"]);
+            }
+            _ => panic!("Expected Run command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_run_with_interpreter() {
+        let cli = Cli::parse_from([
+            "barforge",
+            "run",
+            "--interpreter",
+            "bash",
+            "weather@test",
+            "weather.sh",
+        ]);
+        match cli.command {
+            Some(Commands::Run {
+                interpreter,
+                script,
+                ..
+            }) => {
+                assert_eq!(interpreter.as_deref(), Some("bash"));
+                assert_eq!(script, "weather.sh");
+            }
+            _ => panic!("Expected Run command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_sandbox_test() {
+        let cli = Cli::parse_from(["barforge", "sandbox-test", "weather@test"]);
+        match cli.command {
+            Some(Commands::SandboxTest { uuid }) => assert_eq!(uuid, "weather@test"),
+            _ => panic!("Expected SandboxTest command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_verify_with_optional_uuid() {
+        let cli = Cli::parse_from(["barforge", "verify"]);
+        assert!(matches!(cli.command, Some(Commands::Verify { uuid: None })));
+
+        let cli = Cli::parse_from(["barforge", "verify", "weather@test"]);
+        match cli.command {
+            Some(Commands::Verify { uuid }) => assert_eq!(uuid.as_deref(), Some("weather@test")),
+            _ => panic!("Expected Verify command"),
+        }
+    }
+
     #[test]
     fn cli_parses_internal_sandbox_exec() {
         let cli = Cli::parse_from([
@@ -91,4 +482,28 @@ mod tests {
             _ => panic!("Expected InternalSandboxExec command"),
         }
     }
+
+    #[test]
+    fn cli_parses_audit_with_disable() {
+        let cli = Cli::parse_from(["barforge", "audit"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Audit { disable: false })
+        ));
+
+        let cli = Cli::parse_from(["barforge", "audit", "--disable"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Audit { disable: true })
+        ));
+    }
+
+    #[test]
+    fn cli_parses_diff() {
+        let cli = Cli::parse_from(["barforge", "diff", "clock@test"]);
+        match cli.command {
+            Some(Commands::Diff { uuid }) => assert_eq!(uuid, "clock@test"),
+            _ => panic!("Expected Diff command"),
+        }
+    }
 }
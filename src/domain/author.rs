@@ -11,6 +11,8 @@ pub struct Author {
     pub bio: Option<String>,
     pub website_url: Option<String>,
     pub verified_author: bool,
+    #[serde(default)]
+    pub signing_key: Option<String>,
     pub module_count: u64,
     pub created_at: String,
 }
@@ -48,6 +50,7 @@ mod tests {
             bio: Some("A test author".to_string()),
             website_url: Some("https://example.com".to_string()),
             verified_author: true,
+            signing_key: None,
             module_count: 5,
             created_at: "2025-01-15T00:00:00Z".to_string(),
         }
@@ -82,6 +85,7 @@ mod tests {
             "bio": "I make modules",
             "website_url": "https://dev.example.com",
             "verified_author": true,
+            "signing_key": "RWQBAgMEBQYHCAECAwQFBgcIAQIDBAUGBwgBAgMEBQYHCAECAwQFBgcI",
             "module_count": 10,
             "created_at": "2024-06-01T00:00:00Z"
         }"#;
@@ -89,6 +93,7 @@ mod tests {
         assert_eq!(author.username, "developer");
         assert!(author.verified_author);
         assert_eq!(author.module_count, 10);
+        assert!(author.signing_key.is_some());
     }
 
     #[test]
@@ -107,5 +112,6 @@ mod tests {
         let author: Author = serde_json::from_str(json).unwrap();
         assert_eq!(author.username, "minimal");
         assert!(!author.verified_author);
+        assert!(author.signing_key.is_none());
     }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use super::{ModuleUuid, ModuleVersion};
+use super::{ModuleUuid, ModuleVersion, PermissionSet};
 use serde::{Deserialize, Serialize};
 
 use super::ModulePosition;
@@ -19,6 +19,10 @@ pub struct InstalledModule {
     pub registry_version: Option<ModuleVersion>,
     #[serde(default)]
     pub position: Option<ModulePosition>,
+    #[serde(default)]
+    pub signed_by: Option<String>,
+    #[serde(default)]
+    pub approved_permissions: Option<PermissionSet>,
 }
 
 fn default_installed_at() -> chrono::DateTime<chrono::Utc> {
@@ -61,6 +65,8 @@ mod tests {
             installed_at: chrono::Utc::now(),
             registry_version: None,
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         };
         assert!(module.is_custom_module());
     }
@@ -77,6 +83,8 @@ mod tests {
             installed_at: chrono::Utc::now(),
             registry_version: None,
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         };
         assert!(!module.is_custom_module());
     }
@@ -93,6 +101,8 @@ mod tests {
             installed_at: chrono::Utc::now(),
             registry_version: Some(ModuleVersion::try_from("2.0.0").unwrap()),
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         };
         assert!(module.has_update());
     }
@@ -109,6 +119,8 @@ mod tests {
             installed_at: chrono::Utc::now(),
             registry_version: Some(ModuleVersion::try_from("1.0.0").unwrap()),
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         };
         assert!(!module.has_update());
     }
@@ -125,6 +137,8 @@ mod tests {
             installed_at: chrono::Utc::now(),
             registry_version: None,
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         };
         assert!(!module.has_update());
     }
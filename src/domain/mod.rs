@@ -3,6 +3,7 @@ mod bar_section;
 mod category;
 mod installed;
 mod module;
+mod permissions;
 mod registry;
 mod review;
 
@@ -11,5 +12,6 @@ pub use bar_section::{BarSection, ModulePosition};
 pub use category::ModuleCategory;
 pub use installed::InstalledModule;
 pub use module::{ModuleUuid, ModuleUuidError, ModuleVersion};
+pub use permissions::PermissionSet;
 pub use registry::{CategoryInfo, RegistryIndex, RegistryModule};
 pub use review::{Review, ReviewUser, ReviewsResponse};
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionSet {
+    #[serde(default)]
+    pub network: bool,
+    #[serde(default)]
+    pub udp: bool,
+    #[serde(default)]
+    pub ports: Vec<u16>,
+    #[serde(default)]
+    pub read_paths: Vec<String>,
+    #[serde(default)]
+    pub write_paths: Vec<String>,
+    #[serde(default)]
+    pub runtime_sockets: Vec<String>,
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub install_script: Option<String>,
+}
+
+impl PermissionSet {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.network {
+            if self.ports.is_empty() {
+                lines.push("Network access".to_string());
+            } else {
+                lines.push(format!("Network access on ports {}", join(&self.ports)));
+            }
+        }
+        if self.udp {
+            lines.push("UDP sockets".to_string());
+        }
+        let lists = [
+            ("Read access to", &self.read_paths),
+            ("Write access to", &self.write_paths),
+            ("Runtime sockets:", &self.runtime_sockets),
+            ("Environment variables:", &self.env),
+            ("Dependencies:", &self.dependencies),
+        ];
+        for (label, items) in lists {
+            if !items.is_empty() {
+                lines.push(format!("{label} {}", items.join(", ")));
+            }
+        }
+        if let Some(script) = &self.install_script {
+            lines.push(format!("Runs install script {script}"));
+        }
+        lines
+    }
+
+    pub fn escalations_from(&self, approved: &PermissionSet) -> Vec<String> {
+        let mut added = Vec::new();
+        if self.network && !approved.network {
+            added.push("Network access".to_string());
+        }
+        if self.udp && !approved.udp {
+            added.push("UDP sockets".to_string());
+        }
+        let new_ports: Vec<u16> = self
+            .ports
+            .iter()
+            .copied()
+            .filter(|port| !approved.ports.contains(port))
+            .collect();
+        if !new_ports.is_empty() {
+            added.push(format!("New ports {}", join(&new_ports)));
+        }
+        let lists = [
+            ("Read access to", &self.read_paths, &approved.read_paths),
+            ("Write access to", &self.write_paths, &approved.write_paths),
+            (
+                "Runtime sockets:",
+                &self.runtime_sockets,
+                &approved.runtime_sockets,
+            ),
+            ("Environment variables:", &self.env, &approved.env),
+        ];
+        for (label, requested, granted) in lists {
+            let new: Vec<&str> = requested
+                .iter()
+                .filter(|item| !granted.contains(item))
+                .map(String::as_str)
+                .collect();
+            if !new.is_empty() {
+                added.push(format!("{label} {}", new.join(", ")));
+            }
+        }
+        if let Some(script) = &self.install_script
+            && approved.install_script.is_none()
+        {
+            added.push(format!("New install script {script}"));
+        }
+        added
+    }
+}
+
+fn join(ports: &[u16]) -> String {
+    ports
+        .iter()
+        .map(u16::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_set_is_empty() {
+        assert!(PermissionSet::default().is_empty());
+        assert!(PermissionSet::default().summary().is_empty());
+    }
+
+    #[test]
+    fn summary_lists_requested_access() {
+        let permissions = PermissionSet {
+            network: true,
+            ports: vec![80, 443],
+            write_paths: vec!["~/.cache/weather".to_string()],
+            dependencies: vec!["curl".to_string()],
+            install_script: Some("install.sh".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            permissions.summary(),
+            vec![
+                "Network access on ports 80, 443",
+                "Write access to ~/.cache/weather",
+                "Dependencies: curl",
+                "Runs install script install.sh",
+            ]
+        );
+    }
+
+    #[test]
+    fn escalations_report_only_new_access() {
+        let approved = PermissionSet {
+            network: true,
+            ports: vec![443],
+            write_paths: vec!["~/.cache/weather".to_string()],
+            ..Default::default()
+        };
+        assert!(approved.escalations_from(&approved).is_empty());
+
+        let requested = PermissionSet {
+            network: true,
+            ports: vec![443, 8080],
+            write_paths: vec!["~/.cache/weather".to_string(), "~/.config".to_string()],
+            install_script: Some("install.sh".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            requested.escalations_from(&approved),
+            vec![
+                "New ports 8080",
+                "Write access to ~/.config",
+                "New install script install.sh",
+            ]
+        );
+    }
+
+    #[test]
+    fn reduced_access_is_not_an_escalation() {
+        let approved = PermissionSet {
+            network: true,
+            ports: vec![80, 443],
+            ..Default::default()
+        };
+        let requested = PermissionSet {
+            network: true,
+            ports: vec![443],
+            ..Default::default()
+        };
+        assert!(requested.escalations_from(&approved).is_empty());
+    }
+
+    #[test]
+    fn missing_fields_deserialize_as_empty() {
+        let permissions: PermissionSet = serde_json::from_str(r#"{"network": true}"#).unwrap();
+        assert!(permissions.network);
+        assert!(permissions.ports.is_empty());
+        assert!(permissions.install_script.is_none());
+    }
+}
@@ -54,6 +54,20 @@ fn main() -> iced::Result {
     let cli = Cli::parse();
 
     match cli.command {
+        Some(Commands::Uninstall { uuid, force }) => {
+            setup_tracing();
+            Cli::run_uninstall(uuid, force);
+        }
+        Some(Commands::Run {
+            uuid,
+            script,
+            args,
+            interpreter,
+        }) => Cli::run_module_script(uuid, script, args, interpreter),
+        Some(Commands::SandboxTest { uuid }) => Cli::run_sandbox_test(uuid),
+        Some(Commands::Verify { uuid }) => Cli::run_verify(uuid),
+        Some(Commands::Audit { disable }) => Cli::run_audit(disable),
+        Some(Commands::Diff { uuid }) => Cli::run_diff(uuid),
         Some(Commands::InternalSandboxExec { script, module_dir }) => {
             Cli::run_sandbox_exec(script, module_dir);
         }
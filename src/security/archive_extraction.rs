@@ -1,8 +1,10 @@
 use flate2::read::GzDecoder;
+use std::cell::Cell;
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Component, Path, PathBuf};
-use tar::Archive;
+use std::rc::Rc;
+use tar::{Archive, EntryType};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -25,11 +27,146 @@ pub enum ExtractionError {
     #[error("Archive too large: {size} bytes exceeds {max} bytes")]
     TooLarge { size: u64, max: u64 },
 
+    #[error("Archive unpacks to more than {max} bytes")]
+    UnpackedTooLarge { max: u64 },
+
+    #[error("Archive entry {path} is {size} bytes, exceeding {max} bytes")]
+    FileTooLarge { path: String, size: u64, max: u64 },
+
+    #[error("Archive contains more than {max} entries")]
+    TooManyEntries { max: usize },
+
+    #[error("Archive compression ratio exceeds {max}:1")]
+    CompressionRatio { max: u64 },
+
+    #[error("Device file not allowed in archive: {0}")]
+    DeviceNotAllowed(String),
+
+    #[error("FIFO not allowed in archive: {0}")]
+    FifoNotAllowed(String),
+
+    #[error("Unsupported archive entry type {kind}: {path}")]
+    UnsupportedEntry { path: String, kind: String },
+
+    #[error("Unrecognized archive format")]
+    UnknownFormat,
+
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
     #[error("IO error: {0}")]
     IoError(#[from] io::Error),
 }
 
 pub const MAX_PACKAGE_SIZE: u64 = 50 * 1024 * 1024;
+pub const MAX_UNPACKED_SIZE: u64 = 200 * 1024 * 1024;
+pub const MAX_FILE_SIZE: u64 = 50 * 1024 * 1024;
+pub const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+pub const MAX_COMPRESSION_RATIO: u64 = 100;
+const COMPRESSION_RATIO_GRACE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionLimits {
+    pub max_unpacked_size: u64,
+    pub max_file_size: u64,
+    pub max_entries: usize,
+    pub max_compression_ratio: u64,
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        Self {
+            max_unpacked_size: MAX_UNPACKED_SIZE,
+            max_file_size: MAX_FILE_SIZE,
+            max_entries: MAX_ARCHIVE_ENTRIES,
+            max_compression_ratio: MAX_COMPRESSION_RATIO,
+        }
+    }
+}
+
+struct UnpackBudget {
+    limits: ExtractionLimits,
+    compressed_size: u64,
+    unpacked: u64,
+    entries: usize,
+}
+
+impl UnpackBudget {
+    fn new(limits: ExtractionLimits, compressed_size: u64) -> Self {
+        Self {
+            limits,
+            compressed_size,
+            unpacked: 0,
+            entries: 0,
+        }
+    }
+
+    fn count_entry(&mut self) -> Result<(), ExtractionError> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(ExtractionError::TooManyEntries {
+                max: self.limits.max_entries,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_file(&self, path: &Path, size: u64) -> Result<(), ExtractionError> {
+        if size > self.limits.max_file_size {
+            return Err(ExtractionError::FileTooLarge {
+                path: path.display().to_string(),
+                size,
+                max: self.limits.max_file_size,
+            });
+        }
+        Ok(())
+    }
+
+    fn add_unpacked(&mut self, bytes: u64) -> Result<(), ExtractionError> {
+        self.unpacked += bytes;
+        if self.unpacked > self.limits.max_unpacked_size {
+            return Err(ExtractionError::UnpackedTooLarge {
+                max: self.limits.max_unpacked_size,
+            });
+        }
+        if self.unpacked > COMPRESSION_RATIO_GRACE
+            && self.unpacked > self.compressed_size.max(1) * self.limits.max_compression_ratio
+        {
+            return Err(ExtractionError::CompressionRatio {
+                max: self.limits.max_compression_ratio,
+            });
+        }
+        Ok(())
+    }
+}
+
+struct CountingReader<R> {
+    inner: R,
+    read: Rc<Cell<u64>>,
+    max: u64,
+}
+
+impl<R> CountingReader<R> {
+    fn new(inner: R, read: &Rc<Cell<u64>>, max: u64) -> Self {
+        Self {
+            inner,
+            read: Rc::clone(read),
+            max,
+        }
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let total = self.read.get() + n as u64;
+        self.read.set(total);
+        if total > self.max {
+            return Err(io::Error::other("decompressed size limit exceeded"));
+        }
+        Ok(n)
+    }
+}
 
 #[must_use]
 pub fn normalize_path_algebraic(path: &Path) -> Option<PathBuf> {
@@ -65,63 +202,285 @@ pub fn safe_extraction_path(base: &Path, relative: &Path) -> Result<PathBuf, Ext
     Ok(base.join(normalized))
 }
 
-pub fn extract_tarball_safe(data: &[u8], dest: &Path) -> Result<(), ExtractionError> {
-    if data.len() as u64 > MAX_PACKAGE_SIZE {
-        return Err(ExtractionError::TooLarge {
-            size: data.len() as u64,
-            max: MAX_PACKAGE_SIZE,
-        });
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarGz,
+    TarZst,
+    TarXz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    #[must_use]
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::TarGz)
+        } else if data.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::TarZst)
+        } else if data.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::TarXz)
+        } else if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
     }
+}
 
-    let decoder = GzDecoder::new(data);
-    let mut archive = Archive::new(decoder);
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArchiveExtractor {
+    limits: ExtractionLimits,
+    strip_first_component: bool,
+}
 
-    fs::create_dir_all(dest)?;
+impl ArchiveExtractor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    for entry in archive.entries()? {
-        let mut entry = entry?;
-        let entry_path = entry.path()?;
-        let entry_path = entry_path.as_ref();
+    #[must_use]
+    pub fn with_limits(mut self, limits: ExtractionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
 
-        if entry.header().entry_type().is_symlink() {
-            return Err(ExtractionError::SymlinkNotAllowed(
-                entry_path.display().to_string(),
-            ));
+    #[must_use]
+    pub fn strip_first_component(mut self) -> Self {
+        self.strip_first_component = true;
+        self
+    }
+
+    pub fn extract(&self, data: &[u8], dest: &Path) -> Result<usize, ExtractionError> {
+        if data.len() as u64 > MAX_PACKAGE_SIZE {
+            return Err(ExtractionError::TooLarge {
+                size: data.len() as u64,
+                max: MAX_PACKAGE_SIZE,
+            });
         }
 
-        if entry.header().entry_type().is_hard_link() {
-            return Err(ExtractionError::HardlinkNotAllowed(
-                entry_path.display().to_string(),
-            ));
+        let format = ArchiveFormat::detect(data).ok_or(ExtractionError::UnknownFormat)?;
+        fs::create_dir_all(dest)?;
+
+        let mut unpacker = Unpacker {
+            dest,
+            strip_first_component: self.strip_first_component,
+            budget: UnpackBudget::new(self.limits, data.len() as u64),
+            files: 0,
+        };
+
+        let decompressed = Rc::new(Cell::new(0));
+        let max = self.limits.max_unpacked_size;
+        let result = match format {
+            ArchiveFormat::TarGz => unpacker.tar(CountingReader::new(
+                GzDecoder::new(data),
+                &decompressed,
+                max,
+            )),
+            ArchiveFormat::TarZst => unpacker.tar(CountingReader::new(
+                zstd::Decoder::new(data)?,
+                &decompressed,
+                max,
+            )),
+            ArchiveFormat::TarXz => unpacker.tar(CountingReader::new(
+                liblzma::read::XzDecoder::new(data),
+                &decompressed,
+                max,
+            )),
+            ArchiveFormat::Zip => unpacker.zip(data),
+        };
+        if decompressed.get() > max {
+            return Err(ExtractionError::UnpackedTooLarge { max });
         }
+        result?;
 
-        let safe_path = safe_extraction_path(dest, entry_path)?;
+        Ok(unpacker.files)
+    }
+}
 
-        if let Some(parent) = safe_path.parent() {
-            fs::create_dir_all(parent)?;
+pub fn extract_archive_safe(data: &[u8], dest: &Path) -> Result<(), ExtractionError> {
+    ArchiveExtractor::new().extract(data, dest).map(|_| ())
+}
+
+struct Unpacker<'a> {
+    dest: &'a Path,
+    strip_first_component: bool,
+    budget: UnpackBudget,
+    files: usize,
+}
+
+impl Unpacker<'_> {
+    fn tar(&mut self, reader: impl Read) -> Result<(), ExtractionError> {
+        let mut archive = Archive::new(reader);
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+            let entry_type = entry.header().entry_type();
+
+            self.budget.count_entry()?;
+            check_tar_entry_type(entry_type, &entry_path)?;
+            if entry_type.is_pax_global_extensions() {
+                continue;
+            }
+
+            if entry_type.is_dir() {
+                self.directory(&entry_path)?;
+            } else {
+                let size = entry.size();
+                let mode = entry.header().mode().ok();
+                self.file(&entry_path, size, &mut entry, mode)?;
+            }
         }
 
-        if entry.header().entry_type().is_dir() {
-            fs::create_dir_all(&safe_path)?;
-        } else if entry.header().entry_type().is_file() {
-            let mut file = File::create(&safe_path)?;
-            io::copy(&mut entry, &mut file)?;
+        Ok(())
+    }
 
-            #[cfg(unix)]
-            {
-                use std::os::unix::fs::PermissionsExt;
-                if let Ok(mode) = entry.header().mode() {
-                    let permissions = fs::Permissions::from_mode(mode & 0o755);
-                    fs::set_permissions(&safe_path, permissions)?;
-                }
+    fn zip(&mut self, data: &[u8]) -> Result<(), ExtractionError> {
+        let mut archive = zip::ZipArchive::new(io::Cursor::new(data))?;
+        if archive.len() > self.budget.limits.max_entries {
+            return Err(ExtractionError::TooManyEntries {
+                max: self.budget.limits.max_entries,
+            });
+        }
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            let entry_path = PathBuf::from(entry.name());
+            let mode = entry.unix_mode();
+
+            self.budget.count_entry()?;
+            check_unix_file_type(mode, &entry_path)?;
+
+            if entry.is_dir() {
+                self.directory(&entry_path)?;
+            } else {
+                let size = entry.size();
+                self.file(&entry_path, size, &mut entry, mode)?;
             }
         }
+
+        Ok(())
+    }
+
+    fn destination(&self, entry_path: &Path) -> Result<Option<PathBuf>, ExtractionError> {
+        let relative = if self.strip_first_component {
+            let mut components = entry_path.components();
+            components.next();
+            components.as_path()
+        } else {
+            entry_path
+        };
+        if relative.as_os_str().is_empty() {
+            return Ok(None);
+        }
+
+        let safe_path = safe_extraction_path(self.dest, relative)?;
+        reject_symlinked_ancestors(self.dest, &safe_path)?;
+        Ok(Some(safe_path))
+    }
+
+    fn directory(&mut self, entry_path: &Path) -> Result<(), ExtractionError> {
+        if let Some(path) = self.destination(entry_path)? {
+            fs::create_dir_all(path)?;
+        }
+        Ok(())
     }
 
+    fn file(
+        &mut self,
+        entry_path: &Path,
+        declared_size: u64,
+        contents: &mut dyn Read,
+        mode: Option<u32>,
+    ) -> Result<(), ExtractionError> {
+        let Some(path) = self.destination(entry_path)? else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.budget.check_file(entry_path, declared_size)?;
+
+        let mut file = File::create(&path)?;
+        let written = io::copy(
+            &mut contents.take(self.budget.limits.max_file_size + 1),
+            &mut file,
+        )?;
+        self.budget.check_file(entry_path, written)?;
+        self.budget.add_unpacked(written)?;
+        self.files += 1;
+
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode & 0o755))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn reject_symlinked_ancestors(dest: &Path, target: &Path) -> Result<(), ExtractionError> {
+    let Ok(relative) = target.strip_prefix(dest) else {
+        return Err(ExtractionError::PathTraversal(target.display().to_string()));
+    };
+
+    let mut current = dest.to_path_buf();
+    for component in relative.components() {
+        current.push(component);
+        match fs::symlink_metadata(&current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(ExtractionError::SymlinkNotAllowed(
+                    current.display().to_string(),
+                ));
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
     Ok(())
 }
 
-pub fn extract_tarball_from_reader<R: Read>(
+fn check_tar_entry_type(entry_type: EntryType, path: &Path) -> Result<(), ExtractionError> {
+    let path = path.display().to_string();
+    match entry_type {
+        EntryType::Regular
+        | EntryType::Continuous
+        | EntryType::Directory
+        | EntryType::XGlobalHeader => Ok(()),
+        EntryType::Symlink => Err(ExtractionError::SymlinkNotAllowed(path)),
+        EntryType::Link => Err(ExtractionError::HardlinkNotAllowed(path)),
+        EntryType::Char | EntryType::Block => Err(ExtractionError::DeviceNotAllowed(path)),
+        EntryType::Fifo => Err(ExtractionError::FifoNotAllowed(path)),
+        kind => Err(ExtractionError::UnsupportedEntry {
+            path,
+            kind: format!("{kind:?}"),
+        }),
+    }
+}
+
+fn check_unix_file_type(mode: Option<u32>, path: &Path) -> Result<(), ExtractionError> {
+    const S_IFMT: u32 = 0o170_000;
+
+    let Some(file_type) = mode.map(|mode| mode & S_IFMT).filter(|t| *t != 0) else {
+        return Ok(());
+    };
+    let path = path.display().to_string();
+    match file_type {
+        0o100_000 | 0o040_000 => Ok(()),
+        0o120_000 => Err(ExtractionError::SymlinkNotAllowed(path)),
+        0o020_000 | 0o060_000 => Err(ExtractionError::DeviceNotAllowed(path)),
+        0o010_000 => Err(ExtractionError::FifoNotAllowed(path)),
+        other => Err(ExtractionError::UnsupportedEntry {
+            path,
+            kind: format!("{other:o}"),
+        }),
+    }
+}
+
+pub fn extract_archive_from_reader<R: Read>(
     reader: R,
     dest: &Path,
     max_size: u64,
@@ -137,12 +496,307 @@ pub fn extract_tarball_from_reader<R: Read>(
         });
     }
 
-    extract_tarball_safe(&data, dest)
+    extract_archive_safe(&data, dest)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use tar::{Builder, Header};
+    use tempfile::TempDir;
+
+    fn tar_archive(entries: &[(&str, EntryType, &[u8])]) -> Vec<u8> {
+        let mut builder = Builder::new(Vec::new());
+        for (path, entry_type, content) in entries {
+            let mut header = Header::new_gnu();
+            header.set_entry_type(*entry_type);
+            header.set_size(content.len() as u64);
+            header.set_mode(0o755);
+            builder.append_data(&mut header, path, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn tarball(entries: &[(&str, EntryType, &[u8])]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&tar_archive(entries)).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zip_archive(entries: &[(&str, u32, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+        for (path, mode, content) in entries {
+            let options = zip::write::SimpleFileOptions::default().unix_permissions(*mode);
+            writer.start_file(*path, options).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn round_trips_every_supported_format() {
+        let entries: &[(&str, EntryType, &[u8])] = &[
+            ("module", EntryType::Directory, b""),
+            ("module/run.sh", EntryType::Regular, b"echo hi"),
+        ];
+        let tar = tar_archive(entries);
+        let archives = [
+            (ArchiveFormat::TarGz, tarball(entries)),
+            (
+                ArchiveFormat::TarZst,
+                zstd::encode_all(tar.as_slice(), 3).unwrap(),
+            ),
+            (ArchiveFormat::TarXz, {
+                let mut encoder = liblzma::write::XzEncoder::new(Vec::new(), 6);
+                encoder.write_all(&tar).unwrap();
+                encoder.finish().unwrap()
+            }),
+            (
+                ArchiveFormat::Zip,
+                zip_archive(&[("module/run.sh", 0o100_755, b"echo hi")]),
+            ),
+        ];
+
+        for (format, data) in archives {
+            assert_eq!(ArchiveFormat::detect(&data), Some(format));
+            let dest = TempDir::new().unwrap();
+            let files = ArchiveExtractor::new()
+                .strip_first_component()
+                .extract(&data, dest.path())
+                .unwrap();
+            assert_eq!(files, 1, "{format:?}");
+            assert_eq!(fs::read(dest.path().join("run.sh")).unwrap(), b"echo hi");
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = fs::metadata(dest.path().join("run.sh"))
+                    .unwrap()
+                    .permissions()
+                    .mode();
+                assert_eq!(mode & 0o777, 0o755, "{format:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn counts_payloads_the_tar_reader_skips() {
+        let padding = vec![0u8; 2 * 1024 * 1024];
+        let limits = ExtractionLimits {
+            max_unpacked_size: 1024 * 1024,
+            ..ExtractionLimits::default()
+        };
+
+        for entry_type in [EntryType::Directory, EntryType::XGlobalHeader] {
+            let data = tarball(&[("module", entry_type, &padding)]);
+            let dest = TempDir::new().unwrap();
+            let result = ArchiveExtractor::new()
+                .with_limits(limits)
+                .extract(&data, dest.path());
+            assert!(
+                matches!(result, Err(ExtractionError::UnpackedTooLarge { .. })),
+                "{entry_type:?}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let dest = TempDir::new().unwrap();
+        let result = extract_archive_safe(b"not an archive", dest.path());
+        assert!(matches!(result, Err(ExtractionError::UnknownFormat)));
+    }
+
+    #[test]
+    fn zip_entries_get_the_same_protections() {
+        let dest = TempDir::new().unwrap();
+
+        let traversal = zip_archive(&[("../escape.sh", 0o100_644, b"x")]);
+        assert!(matches!(
+            extract_archive_safe(&traversal, dest.path()),
+            Err(ExtractionError::PathTraversal(_))
+        ));
+
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+        writer
+            .add_symlink(
+                "link",
+                "/etc/passwd",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        let symlink = writer.finish().unwrap().into_inner();
+        assert!(matches!(
+            extract_archive_safe(&symlink, dest.path()),
+            Err(ExtractionError::SymlinkNotAllowed(_))
+        ));
+
+        let limits = ExtractionLimits {
+            max_file_size: 16,
+            ..ExtractionLimits::default()
+        };
+        let big = zip_archive(&[("big", 0o100_644, &[b'a'; 64])]);
+        assert!(matches!(
+            ArchiveExtractor::new()
+                .with_limits(limits)
+                .extract(&big, dest.path()),
+            Err(ExtractionError::FileTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn extracts_regular_files() {
+        let data = tarball(&[
+            ("module", EntryType::Directory, b""),
+            ("module/run.sh", EntryType::Regular, b"echo hi"),
+        ]);
+        let dest = TempDir::new().unwrap();
+        extract_archive_safe(&data, dest.path()).unwrap();
+        assert_eq!(
+            fs::read(dest.path().join("module/run.sh")).unwrap(),
+            b"echo hi"
+        );
+    }
+
+    #[test]
+    fn strips_github_top_level_directory() {
+        let data = tarball(&[
+            (
+                "pax_global_header",
+                EntryType::XGlobalHeader,
+                b"52 comment=abc\n",
+            ),
+            ("owner-repo-abc123", EntryType::Directory, b""),
+            (
+                "owner-repo-abc123/scripts/run.sh",
+                EntryType::Regular,
+                b"echo hi",
+            ),
+        ]);
+        let dest = TempDir::new().unwrap();
+        let files = ArchiveExtractor::new()
+            .strip_first_component()
+            .extract(&data, dest.path())
+            .unwrap();
+        assert_eq!(files, 1);
+        assert!(dest.path().join("scripts/run.sh").is_file());
+        assert!(!dest.path().join("owner-repo-abc123").exists());
+    }
+
+    #[test]
+    fn rejects_symlink_entries() {
+        let data = tarball(&[("link", EntryType::Symlink, b"")]);
+        let dest = TempDir::new().unwrap();
+        let result = ArchiveExtractor::new().extract(&data, dest.path());
+        assert!(matches!(result, Err(ExtractionError::SymlinkNotAllowed(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_to_write_through_existing_symlinks() {
+        let outside = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dest.path().join("config")).unwrap();
+
+        let data = tarball(&[("config/settings.json", EntryType::Regular, b"{}")]);
+        let result = ArchiveExtractor::new().extract(&data, dest.path());
+        assert!(matches!(result, Err(ExtractionError::SymlinkNotAllowed(_))));
+        assert!(!outside.path().join("settings.json").exists());
+    }
+
+    #[test]
+    fn rejects_special_entries() {
+        let dest = TempDir::new().unwrap();
+        let fifo = tarball(&[("pipe", EntryType::Fifo, b"")]);
+        assert!(matches!(
+            extract_archive_safe(&fifo, dest.path()),
+            Err(ExtractionError::FifoNotAllowed(_))
+        ));
+
+        let device = tarball(&[("null", EntryType::Char, b"")]);
+        assert!(matches!(
+            extract_archive_safe(&device, dest.path()),
+            Err(ExtractionError::DeviceNotAllowed(_))
+        ));
+
+        let label = tarball(&[("label", EntryType::new(b'V'), b"")]);
+        assert!(matches!(
+            extract_archive_safe(&label, dest.path()),
+            Err(ExtractionError::UnsupportedEntry { .. })
+        ));
+    }
+
+    #[test]
+    fn entry_count_limit_enforced() {
+        let names: Vec<String> = (0..20).map(|i| format!("f{i}")).collect();
+        let entries: Vec<_> = names
+            .iter()
+            .map(|name| (name.as_str(), EntryType::Regular, &b""[..]))
+            .collect();
+        let limits = ExtractionLimits {
+            max_entries: 10,
+            ..ExtractionLimits::default()
+        };
+        let dest = TempDir::new().unwrap();
+        let result = ArchiveExtractor::new()
+            .with_limits(limits)
+            .extract(&tarball(&entries), dest.path());
+        assert!(matches!(
+            result,
+            Err(ExtractionError::TooManyEntries { max: 10 })
+        ));
+    }
+
+    #[test]
+    fn file_size_limit_enforced() {
+        let content = vec![b'a'; 4096];
+        let limits = ExtractionLimits {
+            max_file_size: 1024,
+            ..ExtractionLimits::default()
+        };
+        let dest = TempDir::new().unwrap();
+        let data = tarball(&[("big", EntryType::Regular, &content)]);
+        let result = ArchiveExtractor::new()
+            .with_limits(limits)
+            .extract(&data, dest.path());
+        assert!(matches!(result, Err(ExtractionError::FileTooLarge { .. })));
+    }
+
+    #[test]
+    fn unpacked_size_limit_enforced() {
+        let content = vec![b'a'; 4096];
+        let limits = ExtractionLimits {
+            max_unpacked_size: 6000,
+            ..ExtractionLimits::default()
+        };
+        let dest = TempDir::new().unwrap();
+        let data = tarball(&[
+            ("one", EntryType::Regular, &content),
+            ("two", EntryType::Regular, &content),
+        ]);
+        let result = ArchiveExtractor::new()
+            .with_limits(limits)
+            .extract(&data, dest.path());
+        assert!(matches!(
+            result,
+            Err(ExtractionError::UnpackedTooLarge { max: 6000 })
+        ));
+    }
+
+    #[test]
+    fn compression_ratio_limit_enforced() {
+        let zeros = vec![0u8; 4 * 1024 * 1024];
+        let dest = TempDir::new().unwrap();
+        let data = tarball(&[("zeros", EntryType::Regular, &zeros)]);
+        let result = extract_archive_safe(&data, dest.path());
+        assert!(matches!(
+            result,
+            Err(ExtractionError::CompressionRatio { .. })
+        ));
+    }
 
     #[test]
     fn algebraic_normalization_handles_simple_path() {
@@ -219,7 +873,7 @@ mod tests {
     #[test]
     fn size_limit_enforced() {
         let data = vec![0u8; (MAX_PACKAGE_SIZE + 1) as usize];
-        let result = extract_tarball_safe(&data, Path::new("/tmp/test"));
+        let result = extract_archive_safe(&data, Path::new("/tmp/test"));
         assert!(matches!(result, Err(ExtractionError::TooLarge { .. })));
     }
 }
@@ -0,0 +1,275 @@
+use super::keyring::{minisign_key_id, signature_key_id};
+use super::verification::VerifyError;
+use crate::services::paths;
+use chrono::{DateTime, Utc};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorKeyPin {
+    pub key_id: String,
+    pub public_key: String,
+    pub pinned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorKeyPins {
+    #[serde(flatten)]
+    pins: HashMap<String, AuthorKeyPin>,
+}
+
+impl AuthorKeyPins {
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from(&paths::author_keys_path())
+    }
+
+    #[must_use]
+    pub fn load_from(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content)
+            .inspect_err(|e| tracing::warn!("Ignoring unreadable author key pins: {e}"))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&paths::author_keys_path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, content)
+    }
+
+    #[must_use]
+    pub fn get(&self, author: &str) -> Option<&AuthorKeyPin> {
+        self.pins.get(author)
+    }
+
+    pub fn pin(&mut self, author: &str, pin: AuthorKeyPin) {
+        self.pins.insert(author.to_string(), pin);
+    }
+
+    pub fn check(
+        &self,
+        author: &str,
+        content: &[u8],
+        co_signature: Option<&str>,
+        profile_key: Option<&str>,
+    ) -> Result<AuthorKeyStatus, VerifyError> {
+        let pinned = self.get(author);
+        let Some(co_signature) = co_signature else {
+            return Ok(match pinned {
+                Some(pin) => AuthorKeyStatus::Missing {
+                    pinned: pin.key_id.clone(),
+                },
+                None => AuthorKeyStatus::Unsigned,
+            });
+        };
+
+        if let Some(pin) = pinned
+            && signature_key_id(co_signature).as_deref() == Some(pin.key_id.as_str())
+        {
+            verify_author_signature(content, co_signature, &pin.public_key)?;
+            return Ok(AuthorKeyStatus::Pinned(pin.key_id.clone()));
+        }
+
+        let public_key = profile_key.ok_or_else(|| {
+            VerifyError::UnknownKey(signature_key_id(co_signature).unwrap_or_default())
+        })?;
+        let presented = AuthorKeyPin {
+            key_id: verify_author_signature(content, co_signature, public_key)?,
+            public_key: public_key.to_string(),
+            pinned_at: Utc::now(),
+        };
+        Ok(match pinned {
+            Some(pin) => AuthorKeyStatus::Changed {
+                pinned: pin.key_id.clone(),
+                presented,
+            },
+            None => AuthorKeyStatus::FirstUse(presented),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorKeyStatus {
+    Unsigned,
+    FirstUse(AuthorKeyPin),
+    Pinned(String),
+    Changed {
+        pinned: String,
+        presented: AuthorKeyPin,
+    },
+    Missing {
+        pinned: String,
+    },
+}
+
+impl AuthorKeyStatus {
+    #[must_use]
+    pub fn warning(&self, author: &str) -> Option<String> {
+        match self {
+            Self::Changed { pinned, presented } => Some(format!(
+                "This release is signed by a different key for {author} ({}) than the one \
+                 pinned on first install ({pinned}). The author's account may have been taken over.",
+                presented.key_id
+            )),
+            Self::Missing { pinned } => Some(format!(
+                "This release is not co-signed by {author}, whose earlier releases were signed \
+                 with key {pinned}. The author's account may have been taken over."
+            )),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn needs_confirmation(&self, approved: Option<&Self>) -> bool {
+        let same_key = |a: &AuthorKeyPin, b: &AuthorKeyPin| {
+            a.key_id == b.key_id && a.public_key == b.public_key
+        };
+        match (self, approved) {
+            (
+                Self::Changed { pinned, presented },
+                Some(Self::Changed {
+                    pinned: approved_pinned,
+                    presented: approved_presented,
+                }),
+            ) => pinned != approved_pinned || !same_key(presented, approved_presented),
+            (Self::Missing { pinned }, Some(Self::Missing { pinned: approved })) => {
+                pinned != approved
+            }
+            (Self::Changed { .. } | Self::Missing { .. }, _) => true,
+            _ => false,
+        }
+    }
+
+    #[must_use]
+    pub fn pin_candidate(&self) -> Option<&AuthorKeyPin> {
+        match self {
+            Self::FirstUse(pin) | Self::Changed { presented: pin, .. } => Some(pin),
+            _ => None,
+        }
+    }
+}
+
+pub fn verify_author_signature(
+    content: &[u8],
+    signature_str: &str,
+    public_key: &str,
+) -> Result<String, VerifyError> {
+    let expected = minisign_key_id(public_key)
+        .ok_or_else(|| VerifyError::InvalidPublicKey("Malformed author key".to_string()))?;
+    let actual = signature_key_id(signature_str)
+        .ok_or_else(|| VerifyError::InvalidSignature("Missing signature key ID".to_string()))?;
+    if expected != actual {
+        return Err(VerifyError::KeyIdMismatch { expected, actual });
+    }
+
+    let key = PublicKey::from_base64(public_key.trim())
+        .map_err(|e| VerifyError::InvalidPublicKey(e.to_string()))?;
+    let signature = Signature::decode(signature_str)
+        .map_err(|e| VerifyError::InvalidSignature(e.to_string()))?;
+    key.verify(content, &signature, false)
+        .map_err(|e| VerifyError::VerificationFailed(e.to_string()))?;
+    Ok(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const AUTHOR_KEY: &str = "RWQBAgMEBQYHCAECAwQFBgcIAQIDBAUGBwgBAgMEBQYHCAECAwQFBgcI";
+    const AUTHOR_KEY_ID: &str = "0807060504030201";
+
+    fn pinned() -> AuthorKeyPins {
+        let mut pins = AuthorKeyPins::default();
+        pins.pin(
+            "jane",
+            AuthorKeyPin {
+                key_id: AUTHOR_KEY_ID.to_string(),
+                public_key: AUTHOR_KEY.to_string(),
+                pinned_at: Utc::now(),
+            },
+        );
+        pins
+    }
+
+    #[test]
+    fn unsigned_release_from_new_author_is_not_a_warning() {
+        let status = AuthorKeyPins::default()
+            .check("jane", b"package", None, None)
+            .unwrap();
+        assert_eq!(status, AuthorKeyStatus::Unsigned);
+        assert!(status.warning("jane").is_none());
+        assert!(status.pin_candidate().is_none());
+    }
+
+    #[test]
+    fn missing_co_signature_after_pinning_warns() {
+        let status = pinned().check("jane", b"package", None, None).unwrap();
+        assert_eq!(
+            status,
+            AuthorKeyStatus::Missing {
+                pinned: AUTHOR_KEY_ID.to_string()
+            }
+        );
+        assert!(status.warning("jane").unwrap().contains(AUTHOR_KEY_ID));
+    }
+
+    #[test]
+    fn co_signature_from_other_key_needs_profile_key() {
+        let signature = "untrusted comment: x\nRWQQEBAQEBAQEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA\n";
+        let result = pinned().check("jane", b"package", Some(signature), None);
+        assert!(matches!(result, Err(VerifyError::UnknownKey(_))));
+    }
+
+    #[test]
+    fn author_signature_must_match_published_key() {
+        let signature = "untrusted comment: x\nRWQQEBAQEBAQEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA\n";
+        let result = verify_author_signature(b"package", signature, AUTHOR_KEY);
+        assert!(matches!(result, Err(VerifyError::KeyIdMismatch { .. })));
+    }
+
+    #[test]
+    fn approval_does_not_cover_a_different_key() {
+        let presented = |key_id: &str| AuthorKeyPin {
+            key_id: key_id.to_string(),
+            public_key: format!("key-{key_id}"),
+            pinned_at: Utc::now(),
+        };
+        let changed = |key_id: &str| AuthorKeyStatus::Changed {
+            pinned: AUTHOR_KEY_ID.to_string(),
+            presented: presented(key_id),
+        };
+
+        let update = changed("1111111111111111");
+        assert!(update.needs_confirmation(None));
+        assert!(update.needs_confirmation(Some(&changed("2222222222222222"))));
+        assert!(!update.needs_confirmation(Some(&changed("1111111111111111"))));
+        assert!(!AuthorKeyStatus::Pinned(AUTHOR_KEY_ID.to_string()).needs_confirmation(None));
+    }
+
+    #[test]
+    fn pins_round_trip_through_disk() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("author_keys.json");
+        let pins = pinned();
+
+        pins.save_to(&path).unwrap();
+
+        assert_eq!(AuthorKeyPins::load_from(&path), pins);
+        assert_eq!(
+            AuthorKeyPins::load_from(&dir.path().join("missing.json")),
+            AuthorKeyPins::default()
+        );
+    }
+}
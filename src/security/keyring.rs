@@ -0,0 +1,406 @@
+use super::verification::VerifyError;
+use crate::services::paths::{self, HTTP_CLIENT, KEY_ROTATION_URL};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use minisign_verify::{PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+const REGISTRY_PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub key_id: String,
+    pub public_key: String,
+    #[serde(default)]
+    pub not_before: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub not_after: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationDocument {
+    #[serde(default)]
+    pub sequence: u64,
+    #[serde(default)]
+    pub keys: Vec<KeyEntry>,
+    #[serde(default)]
+    pub revoked: Vec<String>,
+}
+
+struct TrustedKey {
+    entry: KeyEntry,
+    public_key: PublicKey,
+}
+
+pub struct Keyring {
+    keys: Vec<TrustedKey>,
+    revoked: HashSet<String>,
+    sequence: u64,
+}
+
+impl Keyring {
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut keyring = Self {
+            keys: Vec::new(),
+            revoked: HashSet::new(),
+            sequence: 0,
+        };
+        let entry = KeyEntry {
+            key_id: minisign_key_id(REGISTRY_PUBLIC_KEY)
+                .expect("Compile-time public key must be valid"),
+            public_key: REGISTRY_PUBLIC_KEY.to_string(),
+            not_before: None,
+            not_after: None,
+        };
+        keyring
+            .add(entry)
+            .expect("Compile-time public key must be valid");
+        keyring
+    }
+
+    #[must_use]
+    pub fn load() -> Self {
+        let mut keyring = Self::builtin();
+        keyring.revoked = load_revoked_keys(&paths::revoked_keys_path());
+        let path = paths::key_rotation_path();
+        let (Ok(document), Ok(signature)) = (
+            std::fs::read_to_string(&path),
+            std::fs::read_to_string(paths::signature_path(&path)),
+        ) else {
+            return keyring;
+        };
+        if let Err(e) = keyring.apply_signed_rotation(&document, &signature) {
+            tracing::warn!("Ignoring cached key rotation document: {e}");
+        }
+        keyring
+    }
+
+    pub fn apply_signed_rotation(
+        &mut self,
+        document: &str,
+        signature: &str,
+    ) -> Result<(), VerifyError> {
+        let signer = self.verify(document.as_bytes(), signature)?;
+        let document: RotationDocument = serde_json::from_str(document)
+            .map_err(|e| VerifyError::InvalidKeyring(e.to_string()))?;
+        if document
+            .revoked
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(&signer))
+        {
+            return Err(VerifyError::KeyRevoked(signer));
+        }
+        self.apply_rotation(document)
+    }
+
+    pub fn apply_rotation(&mut self, document: RotationDocument) -> Result<(), VerifyError> {
+        if document.sequence < self.sequence {
+            return Err(VerifyError::RotationRolledBack {
+                fetched: document.sequence,
+                cached: self.sequence,
+            });
+        }
+        for entry in document.keys {
+            self.add(entry)?;
+        }
+        self.revoked
+            .extend(document.revoked.into_iter().map(|id| id.to_uppercase()));
+        self.sequence = document.sequence;
+        Ok(())
+    }
+
+    fn add(&mut self, mut entry: KeyEntry) -> Result<(), VerifyError> {
+        let public_key = PublicKey::from_base64(&entry.public_key)
+            .map_err(|e| VerifyError::InvalidPublicKey(e.to_string()))?;
+        let actual = minisign_key_id(&entry.public_key)
+            .ok_or_else(|| VerifyError::InvalidPublicKey(entry.key_id.clone()))?;
+        entry.key_id = entry.key_id.to_uppercase();
+        if actual != entry.key_id {
+            return Err(VerifyError::KeyIdMismatch {
+                expected: entry.key_id,
+                actual,
+            });
+        }
+
+        self.keys.retain(|key| key.entry.key_id != entry.key_id);
+        self.keys.push(TrustedKey { entry, public_key });
+        Ok(())
+    }
+
+    pub fn key_for(&self, key_id: &str, now: DateTime<Utc>) -> Result<&PublicKey, VerifyError> {
+        if self.revoked.contains(key_id) {
+            return Err(VerifyError::KeyRevoked(key_id.to_string()));
+        }
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.entry.key_id == key_id)
+            .ok_or_else(|| VerifyError::UnknownKey(key_id.to_string()))?;
+
+        let entry = &key.entry;
+        if entry.not_before.is_some_and(|start| now < start)
+            || entry.not_after.is_some_and(|end| now > end)
+        {
+            return Err(VerifyError::KeyNotValid(key_id.to_string()));
+        }
+        Ok(&key.public_key)
+    }
+
+    pub fn verify(&self, content: &[u8], signature_str: &str) -> Result<String, VerifyError> {
+        let key_id = signature_key_id(signature_str)
+            .ok_or_else(|| VerifyError::InvalidSignature("Missing signature key ID".to_string()))?;
+        let key = self.key_for(&key_id, Utc::now())?;
+        let signature = Signature::decode(signature_str)
+            .map_err(|e| VerifyError::InvalidSignature(e.to_string()))?;
+
+        key.verify(content, &signature, false)
+            .map_err(|e| VerifyError::VerificationFailed(e.to_string()))?;
+        Ok(key_id)
+    }
+}
+
+impl Default for Keyring {
+    fn default() -> Self {
+        Self::load()
+    }
+}
+
+pub async fn refresh_key_rotation() -> Result<(), VerifyError> {
+    let fetch = |url: String| async move {
+        HTTP_CLIENT
+            .get(&url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| VerifyError::InvalidKeyring(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| VerifyError::InvalidKeyring(e.to_string()))
+    };
+    let document = fetch(KEY_ROTATION_URL.to_string()).await?;
+    let signature = fetch(format!("{KEY_ROTATION_URL}.minisig")).await?;
+
+    let mut keyring = Keyring::load();
+    keyring.apply_signed_rotation(&document, &signature)?;
+
+    let path = paths::key_rotation_path();
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        save_revoked_keys(&paths::revoked_keys_path(), &keyring.revoked)?;
+        std::fs::write(paths::signature_path(&path), &signature)?;
+        std::fs::write(&path, &document)
+    };
+    write().map_err(|e| VerifyError::InvalidKeyring(e.to_string()))
+}
+
+fn load_revoked_keys(path: &Path) -> HashSet<String> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return HashSet::new();
+    };
+    serde_json::from_str(&content)
+        .inspect_err(|e| tracing::warn!("Ignoring unreadable revoked keys: {e}"))
+        .unwrap_or_default()
+}
+
+fn save_revoked_keys(path: &Path, revoked: &HashSet<String>) -> std::io::Result<()> {
+    let mut revoked: Vec<&String> = revoked.iter().collect();
+    revoked.sort();
+    let content = serde_json::to_string_pretty(&revoked).map_err(std::io::Error::other)?;
+    std::fs::write(path, content)
+}
+
+pub(crate) fn minisign_key_id(encoded: &str) -> Option<String> {
+    let bytes = BASE64.decode(encoded.trim()).ok()?;
+    let id: [u8; 8] = bytes.get(2..10)?.try_into().ok()?;
+    Some(format!("{:016X}", u64::from_le_bytes(id)))
+}
+
+#[must_use]
+pub fn signature_key_id(signature: &str) -> Option<String> {
+    minisign_key_id(signature.lines().nth(1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const ROTATED_KEY: &str = "RWQBAgMEBQYHCAECAwQFBgcIAQIDBAUGBwgBAgMEBQYHCAECAwQFBgcI";
+    const ROTATED_KEY_ID: &str = "0807060504030201";
+
+    fn rotated(not_before: Option<DateTime<Utc>>, not_after: Option<DateTime<Utc>>) -> KeyEntry {
+        KeyEntry {
+            key_id: ROTATED_KEY_ID.to_string(),
+            public_key: ROTATED_KEY.to_string(),
+            not_before,
+            not_after,
+        }
+    }
+
+    #[test]
+    fn registry_key_id_matches_minisign_format() {
+        assert_eq!(
+            minisign_key_id(REGISTRY_PUBLIC_KEY).as_deref(),
+            Some("E7620F1842B4E81F")
+        );
+    }
+
+    #[test]
+    fn rotation_adds_keys_selected_by_id() {
+        let mut keyring = Keyring::builtin();
+        assert!(matches!(
+            keyring.key_for(ROTATED_KEY_ID, Utc::now()),
+            Err(VerifyError::UnknownKey(_))
+        ));
+
+        keyring
+            .apply_rotation(RotationDocument {
+                keys: vec![rotated(None, None)],
+                ..RotationDocument::default()
+            })
+            .unwrap();
+        assert!(keyring.key_for(ROTATED_KEY_ID, Utc::now()).is_ok());
+        assert!(keyring.key_for("E7620F1842B4E81F", Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn rotation_rejects_mismatched_key_id() {
+        let mut entry = rotated(None, None);
+        entry.key_id = "E7620F1842B4E81F".to_string();
+        let result = Keyring::builtin().apply_rotation(RotationDocument {
+            keys: vec![entry],
+            ..RotationDocument::default()
+        });
+        assert!(matches!(result, Err(VerifyError::KeyIdMismatch { .. })));
+    }
+
+    #[test]
+    fn keys_outside_validity_window_are_rejected() {
+        let now = Utc::now();
+        let mut keyring = Keyring::builtin();
+        keyring
+            .apply_rotation(RotationDocument {
+                keys: vec![rotated(Some(now + Duration::days(1)), None)],
+                ..RotationDocument::default()
+            })
+            .unwrap();
+
+        assert!(matches!(
+            keyring.key_for(ROTATED_KEY_ID, now),
+            Err(VerifyError::KeyNotValid(_))
+        ));
+        assert!(
+            keyring
+                .key_for(ROTATED_KEY_ID, now + Duration::days(2))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn revoked_keys_are_rejected() {
+        let mut keyring = Keyring::builtin();
+        keyring
+            .apply_rotation(RotationDocument {
+                revoked: vec!["e7620f1842b4e81f".to_string()],
+                ..RotationDocument::default()
+            })
+            .unwrap();
+        assert!(matches!(
+            keyring.key_for("E7620F1842B4E81F", Utc::now()),
+            Err(VerifyError::KeyRevoked(_))
+        ));
+    }
+
+    #[test]
+    fn older_rotation_documents_are_rejected() {
+        let mut keyring = Keyring::builtin();
+        keyring
+            .apply_rotation(RotationDocument {
+                sequence: 5,
+                keys: vec![rotated(None, None)],
+                ..RotationDocument::default()
+            })
+            .unwrap();
+
+        let result = keyring.apply_rotation(RotationDocument {
+            sequence: 4,
+            ..RotationDocument::default()
+        });
+        assert!(matches!(
+            result,
+            Err(VerifyError::RotationRolledBack {
+                fetched: 4,
+                cached: 5
+            })
+        ));
+        assert!(
+            keyring
+                .apply_rotation(RotationDocument {
+                    sequence: 5,
+                    ..RotationDocument::default()
+                })
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn revocations_outlive_later_documents() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("revoked.json");
+        let mut keyring = Keyring::builtin();
+        keyring
+            .apply_rotation(RotationDocument {
+                sequence: 1,
+                keys: vec![rotated(None, None)],
+                revoked: vec![ROTATED_KEY_ID.to_string()],
+            })
+            .unwrap();
+        save_revoked_keys(&path, &keyring.revoked).unwrap();
+
+        let mut reloaded = Keyring::builtin();
+        reloaded.revoked = load_revoked_keys(&path);
+        reloaded
+            .apply_rotation(RotationDocument {
+                sequence: 2,
+                keys: vec![rotated(None, None)],
+                ..RotationDocument::default()
+            })
+            .unwrap();
+        assert!(matches!(
+            reloaded.key_for(ROTATED_KEY_ID, Utc::now()),
+            Err(VerifyError::KeyRevoked(_))
+        ));
+    }
+
+    #[test]
+    fn rotation_signed_by_revoked_key_is_rejected() {
+        let mut keyring = Keyring::builtin();
+        keyring.revoked.insert("E7620F1842B4E81F".to_string());
+        let signature =
+            "untrusted comment: x\nRWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n";
+
+        let result = keyring.apply_signed_rotation(r#"{"sequence": 9}"#, signature);
+        assert!(matches!(result, Err(VerifyError::KeyRevoked(_))));
+    }
+
+    #[test]
+    fn unsigned_rotation_documents_are_rejected() {
+        let result = Keyring::builtin().apply_signed_rotation(r#"{"keys": []}"#, "not a signature");
+        assert!(matches!(result, Err(VerifyError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn signature_key_id_reads_second_line() {
+        let signature = format!("untrusted comment: x\n{ROTATED_KEY}\ntrusted comment: y\nAAAA\n");
+        assert_eq!(
+            signature_key_id(&signature).as_deref(),
+            Some(ROTATED_KEY_ID)
+        );
+    }
+}
@@ -1,27 +1,50 @@
 pub mod archive_extraction;
+pub mod author_keys;
+pub mod keyring;
 mod path_validation;
+mod python_lexer;
+pub mod resource_limits;
 pub mod revocation;
 pub mod sandbox;
 pub mod script_execution;
 mod script_inspection;
+pub mod seccomp;
+mod shell_lexer;
+#[cfg(feature = "sigstore")]
+mod sigstore_bundle;
 mod url_validation;
 pub mod verification;
 
 pub use archive_extraction::{
-    ExtractionError, MAX_PACKAGE_SIZE, extract_tarball_from_reader, extract_tarball_safe,
-    normalize_path_algebraic, safe_extraction_path,
+    ArchiveExtractor, ArchiveFormat, ExtractionError, ExtractionLimits, MAX_PACKAGE_SIZE,
+    extract_archive_from_reader, extract_archive_safe, normalize_path_algebraic,
+    safe_extraction_path,
 };
+pub use author_keys::{AuthorKeyPin, AuthorKeyPins, AuthorKeyStatus, verify_author_signature};
+pub use keyring::{KeyEntry, Keyring, RotationDocument, refresh_key_rotation, signature_key_id};
 pub use path_validation::{PathTraversalError, validate_extraction_path};
-pub use revocation::{OfflinePolicy, RevocationError, check_revocation};
+pub use resource_limits::{ResourceLimits, user_process_count};
+pub use revocation::{
+    DEFAULT_MAX_LIST_AGE_HOURS, OfflinePolicy, RevocationError, RevocationList, RevocationSettings,
+    RevokedModule, RevokedRelease, check_revocation, current_revocation_list,
+    load_cached_revocation_list, refresh_revocation_list,
+};
 pub use sandbox::{
-    SandboxConfig, SandboxResult, SandboxSeverity, SandboxStatus, apply as apply_sandbox,
-    is_allowed_read_path, is_allowed_write_path, is_available as is_sandbox_available,
+    PathAccess, PathRule, SandboxConfig, SandboxProfile, SandboxResult, SandboxSeverity,
+    SandboxStatus, apply as apply_sandbox, is_allowed_read_path, is_allowed_write_path,
+    is_available as is_sandbox_available,
 };
 pub use script_execution::{
-    SCRIPT_TIMEOUT_SECS, ScriptError, ScriptResult, run_script_sandboxed, run_script_unsandboxed,
+    BASE_ENV_VARS, MAX_OUTPUT_BYTES, OutputStream, SCRIPT_TIMEOUT_SECS, ScriptError, ScriptResult,
+    run_script_sandboxed, run_script_sandboxed_streaming, run_script_unsandboxed,
+    scrub_environment,
+};
+pub use script_inspection::{
+    RiskyPattern, ScriptInspectionResult, ScriptIssue, ScriptLanguage, inspect_script,
+    inspect_script_safety,
 };
-pub use script_inspection::{RiskyPattern, ScriptInspectionResult, inspect_script_safety};
+pub use seccomp::SeccompFilter;
 pub use url_validation::{
     UrlValidationError, parse_github_url_safe, validate_github_url, validate_web_url,
 };
-pub use verification::{Verifier, VerifyError, compute_sha256};
+pub use verification::{SignerIdentity, Verifier, VerifyError, compute_sha256, is_sigstore_bundle};
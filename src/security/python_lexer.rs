@@ -0,0 +1,197 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Name(String),
+    Str(String),
+    Number(String),
+    Op(char),
+    Newline,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[must_use]
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    let mut column = 1;
+    let mut nesting = 0usize;
+
+    let advance = |pos: &mut usize, line: &mut usize, column: &mut usize| {
+        if chars[*pos] == '\n' {
+            *line += 1;
+            *column = 1;
+        } else {
+            *column += 1;
+        }
+        *pos += 1;
+    };
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        let (start_line, start_column) = (line, column);
+
+        if c == '#' {
+            while pos < chars.len() && chars[pos] != '\n' {
+                advance(&mut pos, &mut line, &mut column);
+            }
+        } else if c == '\\' && chars.get(pos + 1) == Some(&'\n') {
+            advance(&mut pos, &mut line, &mut column);
+            advance(&mut pos, &mut line, &mut column);
+        } else if c == '\n' {
+            if nesting == 0 {
+                tokens.push(Token {
+                    kind: TokenKind::Newline,
+                    line,
+                    column,
+                });
+            }
+            advance(&mut pos, &mut line, &mut column);
+        } else if c.is_whitespace() {
+            advance(&mut pos, &mut line, &mut column);
+        } else if let Some(prefix_len) = string_prefix_len(&chars[pos..]) {
+            let raw = chars[pos..pos + prefix_len]
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(&'r'));
+            for _ in 0..prefix_len {
+                advance(&mut pos, &mut line, &mut column);
+            }
+            let quote = chars[pos];
+            let triple = chars.get(pos + 1) == Some(&quote) && chars.get(pos + 2) == Some(&quote);
+            let delimiter_len = if triple { 3 } else { 1 };
+            for _ in 0..delimiter_len {
+                advance(&mut pos, &mut line, &mut column);
+            }
+
+            let mut value = String::new();
+            while pos < chars.len() {
+                let c = chars[pos];
+                if c == quote
+                    && (!triple
+                        || (chars.get(pos + 1) == Some(&quote)
+                            && chars.get(pos + 2) == Some(&quote)))
+                {
+                    for _ in 0..delimiter_len {
+                        advance(&mut pos, &mut line, &mut column);
+                    }
+                    break;
+                }
+                if c == '\n' && !triple {
+                    break;
+                }
+                if c == '\\' && pos + 1 < chars.len() {
+                    advance(&mut pos, &mut line, &mut column);
+                    if raw {
+                        value.push('\\');
+                    }
+                    value.push(chars[pos]);
+                } else {
+                    value.push(c);
+                }
+                advance(&mut pos, &mut line, &mut column);
+            }
+
+            tokens.push(Token {
+                kind: TokenKind::Str(value),
+                line: start_line,
+                column: start_column,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                name.push(chars[pos]);
+                advance(&mut pos, &mut line, &mut column);
+            }
+            tokens.push(Token {
+                kind: TokenKind::Name(name),
+                line: start_line,
+                column: start_column,
+            });
+        } else if c.is_ascii_digit() {
+            let mut number = String::new();
+            while pos < chars.len() && (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                number.push(chars[pos]);
+                advance(&mut pos, &mut line, &mut column);
+            }
+            tokens.push(Token {
+                kind: TokenKind::Number(number),
+                line: start_line,
+                column: start_column,
+            });
+        } else {
+            match c {
+                '(' | '[' | '{' => nesting += 1,
+                ')' | ']' | '}' => nesting = nesting.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(Token {
+                kind: TokenKind::Op(c),
+                line: start_line,
+                column: start_column,
+            });
+            advance(&mut pos, &mut line, &mut column);
+        }
+    }
+
+    tokens
+}
+
+fn string_prefix_len(chars: &[char]) -> Option<usize> {
+    let prefix_len = chars
+        .iter()
+        .take(2)
+        .take_while(|c| matches!(c.to_ascii_lowercase(), 'r' | 'b' | 'f' | 'u'))
+        .count();
+    matches!(chars.get(prefix_len), Some('"' | '\'')).then_some(prefix_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_comments_and_decodes_strings() {
+        let tokens = tokenize("x = 'a\\'b'  # import socket\n");
+        let kinds: Vec<_> = tokens.into_iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Name("x".into()),
+                TokenKind::Op('='),
+                TokenKind::Str("a'b".into()),
+                TokenKind::Newline,
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_prefixed_and_triple_quoted_strings() {
+        let tokens = tokenize("s = rb'\\d'\nt = \"\"\"one\ntwo\"\"\"\nu = 1");
+        let strings: Vec<_> = tokens
+            .iter()
+            .filter_map(|t| match &t.kind {
+                TokenKind::Str(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(strings, vec!["\\d", "one\ntwo"]);
+        let last = tokens.last().unwrap();
+        assert_eq!((last.line, last.column), (4, 5));
+    }
+
+    #[test]
+    fn suppresses_newlines_inside_brackets() {
+        let tokens = tokenize("f(\n  1,\n)\n");
+        let newlines = tokens
+            .iter()
+            .filter(|t| t.kind == TokenKind::Newline)
+            .count();
+        assert_eq!(newlines, 1);
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+
+const MAX_LIMITS: ResourceLimits = ResourceLimits {
+    cpu_seconds: 600,
+    memory_mb: 8192,
+    processes: 512,
+    open_files: 4096,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    pub cpu_seconds: u64,
+    pub memory_mb: u64,
+    pub processes: u64,
+    pub open_files: u64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            cpu_seconds: 60,
+            memory_mb: 2048,
+            processes: 64,
+            open_files: 256,
+        }
+    }
+}
+
+impl ResourceLimits {
+    #[must_use]
+    pub fn clamped(&self) -> Self {
+        Self {
+            cpu_seconds: self.cpu_seconds.clamp(1, MAX_LIMITS.cpu_seconds),
+            memory_mb: self.memory_mb.clamp(64, MAX_LIMITS.memory_mb),
+            processes: self.processes.clamp(1, MAX_LIMITS.processes),
+            open_files: self.open_files.clamp(16, MAX_LIMITS.open_files),
+        }
+    }
+
+    pub fn apply(&self, running_processes: u64) -> io::Result<()> {
+        set_limit(libc::RLIMIT_CPU, self.cpu_seconds)?;
+        set_limit(libc::RLIMIT_AS, self.memory_mb * 1024 * 1024)?;
+        set_limit(libc::RLIMIT_NPROC, running_processes + self.processes)?;
+        set_limit(libc::RLIMIT_NOFILE, self.open_files)
+    }
+}
+
+impl fmt::Display for ResourceLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cpu {}s, memory {} MiB, {} extra processes, {} open files",
+            self.cpu_seconds, self.memory_mb, self.processes, self.open_files
+        )
+    }
+}
+
+fn set_limit(resource: libc::__rlimit_resource_t, value: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: value,
+        rlim_max: value,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn user_process_count() -> u64 {
+    let uid = unsafe { libc::getuid() };
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().parse::<u32>().is_ok())
+        .filter(|e| e.metadata().is_ok_and(|m| m.uid() == uid))
+        .count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_package_limits_to_ceiling() {
+        let requested = ResourceLimits {
+            cpu_seconds: 0,
+            memory_mb: 1_000_000,
+            processes: 10_000,
+            open_files: 8,
+        };
+        let clamped = requested.clamped();
+        assert_eq!(clamped.cpu_seconds, 1);
+        assert_eq!(clamped.memory_mb, MAX_LIMITS.memory_mb);
+        assert_eq!(clamped.processes, MAX_LIMITS.processes);
+        assert_eq!(clamped.open_files, 16);
+    }
+
+    #[test]
+    fn partial_limits_fill_defaults() {
+        let limits: ResourceLimits = toml::from_str("memory_mb = 512").unwrap();
+        assert_eq!(limits.memory_mb, 512);
+        assert_eq!(limits.cpu_seconds, ResourceLimits::default().cpu_seconds);
+    }
+
+    #[test]
+    fn counts_own_processes() {
+        assert!(user_process_count() >= 1);
+    }
+}
@@ -1,6 +1,11 @@
-use crate::services::paths::SECURITY_CHECK_URL;
+use super::verification::{Verifier, VerifyError};
+use crate::services::paths::{self, HTTP_CLIENT, REVOCATION_LIST_URL, SECURITY_CHECK_URL};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub const DEFAULT_MAX_LIST_AGE_HOURS: u32 = 72;
+
 #[derive(Debug, Error)]
 pub enum RevocationError {
     #[error("Module {uuid} version {version} has been revoked: {reason}")]
@@ -18,15 +23,130 @@ pub enum RevocationError {
 
     #[error("Security check timed out")]
     Timeout,
+
+    #[error("Revocation list signature rejected: {0}")]
+    InvalidSignature(#[from] VerifyError),
+
+    #[error("Revocation list from {fetched} is older than the cached list from {cached}")]
+    RolledBack {
+        fetched: DateTime<Utc>,
+        cached: DateTime<Utc>,
+    },
+
+    #[error("No revocation data from the last {0} hours is available")]
+    Stale(u32),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OfflinePolicy {
     AllowOffline,
     #[default]
     FailClosed,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationSettings {
+    #[serde(default)]
+    pub offline_policy: OfflinePolicy,
+    #[serde(default = "default_max_list_age_hours")]
+    pub max_list_age_hours: u32,
+    #[serde(default)]
+    pub auto_disable_revoked: bool,
+}
+
+fn default_max_list_age_hours() -> u32 {
+    DEFAULT_MAX_LIST_AGE_HOURS
+}
+
+impl Default for RevocationSettings {
+    fn default() -> Self {
+        Self {
+            offline_policy: OfflinePolicy::default(),
+            max_list_age_hours: DEFAULT_MAX_LIST_AGE_HOURS,
+            auto_disable_revoked: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevokedRelease {
+    pub uuid: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokedModule {
+    pub uuid: String,
+    pub version: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationList {
+    pub generated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub revoked: Vec<RevokedRelease>,
+}
+
+impl RevocationList {
+    pub fn parse_signed(document: &str, signature: &str) -> Result<Self, RevocationError> {
+        Verifier::new().verify(document.as_bytes(), signature)?;
+        serde_json::from_str(document).map_err(|e| RevocationError::InvalidResponse(e.to_string()))
+    }
+
+    #[must_use]
+    pub fn find(&self, uuid: &str, version: &str) -> Option<&RevokedRelease> {
+        self.revoked.iter().find(|release| {
+            release.uuid == uuid && release.version.as_deref().is_none_or(|v| v == version)
+        })
+    }
+
+    pub fn check(&self, uuid: &str, version: &str) -> Result<(), RevocationError> {
+        match self.find(uuid, version) {
+            Some(release) => Err(RevocationError::Revoked {
+                uuid: uuid.to_string(),
+                version: version.to_string(),
+                reason: release
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| "No reason provided".to_string()),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn audit<'a>(
+        &self,
+        installed: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<RevokedModule> {
+        installed
+            .into_iter()
+            .filter_map(|(uuid, version)| match self.check(uuid, version) {
+                Err(RevocationError::Revoked {
+                    uuid,
+                    version,
+                    reason,
+                }) => Some(RevokedModule {
+                    uuid,
+                    version,
+                    reason,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_fresh(&self, max_age_hours: u32, now: DateTime<Utc>) -> bool {
+        now - self.generated_at <= Duration::hours(i64::from(max_age_hours))
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct SecurityCheckResponse {
     revoked: bool,
@@ -36,7 +156,7 @@ struct SecurityCheckResponse {
 pub async fn check_revocation(
     uuid: &str,
     version: &str,
-    policy: OfflinePolicy,
+    settings: RevocationSettings,
 ) -> Result<(), RevocationError> {
     let encoded_uuid = urlencoding::encode(uuid);
     let encoded_version = urlencoding::encode(version);
@@ -49,21 +169,9 @@ pub async fn check_revocation(
 
     let response = match client.get(&url).send().await {
         Ok(resp) => resp,
-        Err(e) if e.is_timeout() => return Err(RevocationError::Timeout),
-        Err(e) if e.is_connect() => {
-            return match policy {
-                OfflinePolicy::AllowOffline => {
-                    tracing::warn!(
-                        "Revocation check skipped (offline mode): {} v{}",
-                        uuid,
-                        version
-                    );
-                    Ok(())
-                }
-                OfflinePolicy::FailClosed => Err(RevocationError::NetworkError(format!(
-                    "Cannot verify module safety (network unavailable): {e}"
-                ))),
-            };
+        Err(e) if e.is_timeout() || e.is_connect() => {
+            tracing::warn!("Live revocation check unavailable, using cached list: {e}");
+            return check_offline(uuid, version, settings);
         }
         Err(e) => return Err(RevocationError::NetworkError(e.to_string())),
     };
@@ -90,6 +198,103 @@ pub async fn check_revocation(
         });
     }
 
+    if let Err(e) = refresh_revocation_list().await {
+        tracing::warn!("Failed to refresh revocation list: {e}");
+    }
+    Ok(())
+}
+
+fn check_offline(
+    uuid: &str,
+    version: &str,
+    settings: RevocationSettings,
+) -> Result<(), RevocationError> {
+    let max_age = settings.max_list_age_hours;
+    match load_cached_revocation_list() {
+        Some(list) if list.is_fresh(max_age, Utc::now()) => list.check(uuid, version),
+        _ => match settings.offline_policy {
+            OfflinePolicy::AllowOffline => {
+                tracing::warn!(
+                    "Revocation check skipped (offline mode): {} v{}",
+                    uuid,
+                    version
+                );
+                Ok(())
+            }
+            OfflinePolicy::FailClosed => Err(RevocationError::Stale(max_age)),
+        },
+    }
+}
+
+#[must_use]
+pub fn load_cached_revocation_list() -> Option<RevocationList> {
+    let path = paths::revocation_list_path();
+    let document = std::fs::read_to_string(&path).ok()?;
+    let signature = std::fs::read_to_string(paths::signature_path(&path)).ok()?;
+    RevocationList::parse_signed(&document, &signature)
+        .inspect_err(|e| tracing::warn!("Ignoring cached revocation list: {e}"))
+        .ok()
+}
+
+pub async fn refresh_revocation_list() -> Result<RevocationList, RevocationError> {
+    let fetch = |url: String| async move {
+        HTTP_CLIENT
+            .get(&url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| RevocationError::NetworkError(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| RevocationError::NetworkError(e.to_string()))
+    };
+    let document = fetch(REVOCATION_LIST_URL.to_string()).await?;
+    let signature = fetch(format!("{REVOCATION_LIST_URL}.minisig")).await?;
+    let list = RevocationList::parse_signed(&document, &signature)?;
+
+    if let Some(cached) = load_cached_revocation_list() {
+        check_not_rolled_back(&list, &cached)?;
+    }
+
+    let path = paths::revocation_list_path();
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(paths::signature_path(&path), &signature)?;
+        std::fs::write(&path, &document)
+    };
+    if let Err(e) = write() {
+        tracing::warn!("Failed to cache revocation list: {e}");
+    }
+    Ok(list)
+}
+
+pub async fn current_revocation_list(
+    max_age_hours: u32,
+) -> Result<RevocationList, RevocationError> {
+    let error = match refresh_revocation_list().await {
+        Ok(list) => return Ok(list),
+        Err(e) => e,
+    };
+    tracing::warn!("Failed to refresh revocation list: {error}");
+    match load_cached_revocation_list() {
+        Some(list) if list.is_fresh(max_age_hours, Utc::now()) => Ok(list),
+        Some(_) => Err(RevocationError::Stale(max_age_hours)),
+        None => Err(error),
+    }
+}
+
+fn check_not_rolled_back(
+    fetched: &RevocationList,
+    cached: &RevocationList,
+) -> Result<(), RevocationError> {
+    if fetched.generated_at < cached.generated_at {
+        return Err(RevocationError::RolledBack {
+            fetched: fetched.generated_at,
+            cached: cached.generated_at,
+        });
+    }
     Ok(())
 }
 
@@ -115,6 +320,81 @@ mod tests {
         assert!(msg.contains("Security vulnerability"));
     }
 
+    fn list(generated_at: DateTime<Utc>) -> RevocationList {
+        RevocationList {
+            generated_at,
+            revoked: vec![
+                RevokedRelease {
+                    uuid: "weather@test".to_string(),
+                    version: Some("1.0.0".to_string()),
+                    reason: Some("Malware".to_string()),
+                },
+                RevokedRelease {
+                    uuid: "miner@test".to_string(),
+                    version: None,
+                    reason: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn list_matches_specific_and_all_versions() {
+        let list = list(Utc::now());
+        assert!(matches!(
+            list.check("weather@test", "1.0.0"),
+            Err(RevocationError::Revoked { .. })
+        ));
+        assert!(list.check("weather@test", "1.0.1").is_ok());
+        assert!(list.find("miner@test", "9.9.9").is_some());
+        assert!(list.check("clock@test", "1.0.0").is_ok());
+    }
+
+    #[test]
+    fn audit_reports_only_revoked_installs() {
+        let list = list(Utc::now());
+        let revoked = list.audit([
+            ("weather@test", "1.0.0"),
+            ("weather@test", "2.0.0"),
+            ("miner@test", "0.1.0"),
+            ("clock@test", "1.0.0"),
+        ]);
+
+        let uuids: Vec<&str> = revoked.iter().map(|r| r.uuid.as_str()).collect();
+        assert_eq!(uuids, vec!["weather@test", "miner@test"]);
+        assert_eq!(revoked[0].reason, "Malware");
+    }
+
+    #[test]
+    fn list_freshness_uses_window() {
+        let now = Utc::now();
+        let list = list(now - Duration::hours(10));
+        assert!(list.is_fresh(24, now));
+        assert!(!list.is_fresh(6, now));
+    }
+
+    #[test]
+    fn older_lists_are_rejected_as_rollbacks() {
+        let now = Utc::now();
+        let cached = list(now);
+        assert!(check_not_rolled_back(&list(now - Duration::hours(1)), &cached).is_err());
+        assert!(check_not_rolled_back(&list(now + Duration::hours(1)), &cached).is_ok());
+    }
+
+    #[test]
+    fn unsigned_lists_are_rejected() {
+        let document = serde_json::to_string(&list(Utc::now())).unwrap();
+        let result = RevocationList::parse_signed(&document, "untrusted comment: x\nforged\n");
+        assert!(matches!(result, Err(RevocationError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn revocation_settings_default_from_empty_json() {
+        let settings: RevocationSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, RevocationSettings::default());
+        assert_eq!(settings.max_list_age_hours, DEFAULT_MAX_LIST_AGE_HOURS);
+    }
+
     #[test]
     fn url_encoding_handles_special_chars() {
         let uuid = "test/uuid+special";
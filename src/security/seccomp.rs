@@ -0,0 +1,258 @@
+use std::io;
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_003E);
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH: Option<u32> = Some(0xC000_00B7);
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const AUDIT_ARCH: Option<u32> = None;
+
+#[cfg(target_arch = "x86_64")]
+const X32_SYSCALL_BIT: Option<u32> = Some(0x4000_0000);
+#[cfg(not(target_arch = "x86_64"))]
+const X32_SYSCALL_BIT: Option<u32> = None;
+
+const SECCOMP_DATA_NR: u32 = 0;
+const SECCOMP_DATA_ARCH: u32 = 4;
+const SECCOMP_DATA_ARGS: u32 = 16;
+const SOCK_TYPE_MASK: u32 = 0xf;
+const CLONE_NAMESPACE_FLAGS: u32 = (libc::CLONE_NEWNS
+    | libc::CLONE_NEWCGROUP
+    | libc::CLONE_NEWUTS
+    | libc::CLONE_NEWIPC
+    | libc::CLONE_NEWUSER
+    | libc::CLONE_NEWPID
+    | libc::CLONE_NEWNET
+    | libc::CLONE_NEWTIME) as u32;
+
+const DENIED_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_unshare,
+    libc::SYS_setns,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_io_uring_setup,
+    libc::SYS_kexec_load,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_reboot,
+];
+
+pub struct SeccompFilter {
+    program: Vec<libc::sock_filter>,
+}
+
+impl SeccompFilter {
+    #[must_use]
+    pub fn new(allow_udp: bool) -> Option<Self> {
+        let arch = AUDIT_ARCH?;
+        let deny = libc::SECCOMP_RET_ERRNO | libc::EPERM as u32;
+        let mut program = vec![
+            load(SECCOMP_DATA_ARCH),
+            jump_eq(arch, 1, 0),
+            ret(libc::SECCOMP_RET_KILL_PROCESS),
+            load(SECCOMP_DATA_NR),
+        ];
+        if let Some(x32) = X32_SYSCALL_BIT {
+            program.extend([jump_ge(x32, 0, 1), ret(deny)]);
+        }
+
+        for syscall in DENIED_SYSCALLS {
+            program.push(jump_eq(*syscall as u32, 0, 1));
+            program.push(ret(deny));
+        }
+
+        program.extend([
+            jump_eq(libc::SYS_clone3 as u32, 0, 1),
+            ret(libc::SECCOMP_RET_ERRNO | libc::ENOSYS as u32),
+            jump_eq(libc::SYS_clone as u32, 0, 4),
+            load(SECCOMP_DATA_ARGS),
+            jump_set(CLONE_NAMESPACE_FLAGS, 0, 1),
+            ret(deny),
+            ret(libc::SECCOMP_RET_ALLOW),
+        ]);
+
+        let type_checks: u8 = if allow_udp { 4 } else { 6 };
+        program.extend([
+            jump_eq(libc::SYS_socket as u32, 1, 0),
+            ret(libc::SECCOMP_RET_ALLOW),
+            load(SECCOMP_DATA_ARGS),
+            jump_eq(libc::AF_PACKET as u32, 0, 1),
+            ret(deny),
+            jump_eq(libc::AF_INET as u32, 1, 0),
+            jump_eq(libc::AF_INET6 as u32, 0, type_checks),
+            load(SECCOMP_DATA_ARGS + 8),
+            stmt(libc::BPF_ALU | libc::BPF_AND | libc::BPF_K, SOCK_TYPE_MASK),
+            jump_eq(libc::SOCK_RAW as u32, 0, 1),
+            ret(deny),
+        ]);
+        if !allow_udp {
+            program.extend([jump_eq(libc::SOCK_DGRAM as u32, 0, 1), ret(deny)]);
+        }
+        program.push(ret(libc::SECCOMP_RET_ALLOW));
+
+        Some(Self { program })
+    }
+
+    pub fn install(&self) -> io::Result<()> {
+        let program = libc::sock_fprog {
+            len: self.program.len() as libc::c_ushort,
+            filter: self.program.as_ptr().cast_mut(),
+        };
+
+        unsafe {
+            if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &raw const program,
+            ) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn stmt(code: u32, k: u32) -> libc::sock_filter {
+    libc::sock_filter {
+        code: code as u16,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+fn load(offset: u32) -> libc::sock_filter {
+    stmt(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, offset)
+}
+
+fn ret(action: u32) -> libc::sock_filter {
+    stmt(libc::BPF_RET | libc::BPF_K, action)
+}
+
+fn jump_eq(value: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+        code: (libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K) as u16,
+        jt,
+        jf,
+        k: value,
+    }
+}
+
+fn jump_set(value: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+        code: (libc::BPF_JMP | libc::BPF_JSET | libc::BPF_K) as u16,
+        jt,
+        jf,
+        k: value,
+    }
+}
+
+fn jump_ge(value: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+        code: (libc::BPF_JMP | libc::BPF_JGE | libc::BPF_K) as u16,
+        jt,
+        jf,
+        k: value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{TcpListener, UdpSocket};
+
+    fn filtered<T: Send + 'static>(allow_udp: bool, probe: fn() -> T) -> T {
+        std::thread::spawn(move || {
+            SeccompFilter::new(allow_udp).unwrap().install().unwrap();
+            probe()
+        })
+        .join()
+        .unwrap()
+    }
+
+    #[test]
+    fn denies_udp_unless_permitted() {
+        assert!(filtered(false, || UdpSocket::bind("127.0.0.1:0").is_err()));
+        assert!(filtered(true, || UdpSocket::bind("127.0.0.1:0").is_ok()));
+    }
+
+    #[test]
+    fn allows_tcp_and_unix_sockets() {
+        assert!(filtered(false, || TcpListener::bind("127.0.0.1:0").is_ok()));
+        assert!(filtered(false, || {
+            std::os::unix::net::UnixDatagram::unbound().is_ok()
+        }));
+    }
+
+    #[test]
+    fn denies_namespace_and_mount_syscalls() {
+        let errno = filtered(false, || {
+            let rc = unsafe { libc::unshare(0) };
+            (rc, io::Error::last_os_error().raw_os_error())
+        });
+        assert_eq!(errno, (-1, Some(libc::EPERM)));
+    }
+
+    #[test]
+    fn denies_clone_into_new_namespaces() {
+        let errno = filtered(false, || {
+            let rc = unsafe {
+                libc::syscall(
+                    libc::SYS_clone,
+                    libc::CLONE_NEWUSER | libc::CLONE_NEWNET | libc::SIGCHLD,
+                    0,
+                    0,
+                    0,
+                    0,
+                )
+            };
+            if rc == 0 {
+                unsafe { libc::_exit(0) };
+            }
+            (rc, io::Error::last_os_error().raw_os_error())
+        });
+        assert_eq!(errno, (-1, Some(libc::EPERM)));
+    }
+
+    #[test]
+    fn reports_clone3_as_unsupported_and_still_spawns_processes() {
+        let errno = filtered(false, || {
+            let rc = unsafe { libc::syscall(libc::SYS_clone3, std::ptr::null::<u8>(), 0) };
+            (rc, io::Error::last_os_error().raw_os_error())
+        });
+        assert_eq!(errno, (-1, Some(libc::ENOSYS)));
+
+        assert!(filtered(false, || {
+            std::process::Command::new("true")
+                .status()
+                .is_ok_and(|s| s.success())
+        }));
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn denies_x32_syscalls() {
+        let errno = filtered(false, || {
+            let rc = unsafe { libc::syscall(0x4000_0000 | libc::SYS_getpid) };
+            (rc, io::Error::last_os_error().raw_os_error())
+        });
+        assert_eq!(errno, (-1, Some(libc::EPERM)));
+    }
+}
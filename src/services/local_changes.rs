@@ -0,0 +1,305 @@
+use crate::services::install_manifest::{ManifestError, verify_module_integrity};
+use crate::services::paths;
+use std::fs;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+pub const ORIG_SUFFIX: &str = ".orig";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocalChangesPolicy {
+    Keep,
+    Overwrite,
+    #[default]
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Kept,
+    Overwritten,
+    Merged,
+    Conflicted,
+    Restored,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedFile {
+    pub path: String,
+    pub resolution: Resolution,
+}
+
+#[derive(Debug)]
+struct LocalFile {
+    path: String,
+    mode: u32,
+    local: Vec<u8>,
+    base: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+pub struct LocalChanges {
+    modified: Vec<LocalFile>,
+    added: Vec<LocalFile>,
+}
+
+impl LocalChanges {
+    pub fn capture(uuid: &str, module_dir: &Path) -> Result<Self, ManifestError> {
+        let report = match verify_module_integrity(uuid, module_dir) {
+            Ok(report) => report,
+            Err(ManifestError::NotRecorded(_)) => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        let baseline_dir = paths::module_baseline_dir(uuid);
+
+        let mut changes = Self::default();
+        for path in report.modified {
+            let base = fs::read(baseline_dir.join(&path)).ok();
+            if let Some(file) = read_local(module_dir, path, base)? {
+                changes.modified.push(file);
+            }
+        }
+        for path in report.extra {
+            if let Some(file) = read_local(module_dir, path, None)? {
+                changes.added.push(file);
+            }
+        }
+        Ok(changes)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.added.is_empty()
+    }
+
+    #[must_use]
+    pub fn paths(&self) -> Vec<String> {
+        self.modified
+            .iter()
+            .chain(&self.added)
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    pub fn apply(
+        self,
+        module_dir: &Path,
+        policy: LocalChangesPolicy,
+    ) -> io::Result<Vec<ResolvedFile>> {
+        let mut resolved = Vec::new();
+
+        for file in self.modified {
+            let target = module_dir.join(&file.path);
+            let upstream = fs::read(&target).ok();
+
+            let resolution = match (policy, upstream) {
+                (_, Some(upstream)) if upstream == file.local => Resolution::Kept,
+                (LocalChangesPolicy::Overwrite, _) => {
+                    write_orig(&target, &file.local)?;
+                    Resolution::Overwritten
+                }
+                (LocalChangesPolicy::Merge, Some(upstream)) => {
+                    match three_way_merge(file.base.as_deref(), &file.local, &upstream) {
+                        Some((merged, clean)) => {
+                            fs::write(&target, merged)?;
+                            write_orig(&target, &file.local)?;
+                            if clean {
+                                Resolution::Merged
+                            } else {
+                                Resolution::Conflicted
+                            }
+                        }
+                        None => {
+                            restore(&target, &file)?;
+                            Resolution::Kept
+                        }
+                    }
+                }
+                (LocalChangesPolicy::Keep | LocalChangesPolicy::Merge, _) => {
+                    restore(&target, &file)?;
+                    Resolution::Kept
+                }
+            };
+            resolved.push(ResolvedFile {
+                path: file.path,
+                resolution,
+            });
+        }
+
+        for file in self.added {
+            let target = module_dir.join(&file.path);
+            let resolution = match fs::read(&target) {
+                Ok(upstream) if upstream == file.local => Resolution::Restored,
+                Ok(_) => {
+                    write_orig(&target, &file.local)?;
+                    Resolution::Overwritten
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    restore(&target, &file)?;
+                    Resolution::Restored
+                }
+                Err(e) => return Err(e),
+            };
+            resolved.push(ResolvedFile {
+                path: file.path,
+                resolution,
+            });
+        }
+
+        Ok(resolved)
+    }
+}
+
+fn read_local(
+    module_dir: &Path,
+    path: String,
+    base: Option<Vec<u8>>,
+) -> io::Result<Option<LocalFile>> {
+    let full_path = module_dir.join(&path);
+    let metadata = fs::symlink_metadata(&full_path)?;
+    if !metadata.is_file() {
+        return Ok(None);
+    }
+    Ok(Some(LocalFile {
+        path,
+        mode: metadata.permissions().mode() & 0o777,
+        local: fs::read(&full_path)?,
+        base,
+    }))
+}
+
+fn three_way_merge(base: Option<&[u8]>, local: &[u8], upstream: &[u8]) -> Option<(String, bool)> {
+    let base = std::str::from_utf8(base?).ok()?;
+    let local = std::str::from_utf8(local).ok()?;
+    let upstream = std::str::from_utf8(upstream).ok()?;
+    Some(match diffy::merge(base, local, upstream) {
+        Ok(merged) => (merged, true),
+        Err(conflicted) => (conflicted, false),
+    })
+}
+
+fn restore(target: &Path, file: &LocalFile) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target, &file.local)?;
+    fs::set_permissions(target, fs::Permissions::from_mode(file.mode))
+}
+
+fn write_orig(target: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut orig = target.as_os_str().to_owned();
+    orig.push(ORIG_SUFFIX);
+    fs::write(orig, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn changes(path: &str, base: &str, local: &str) -> LocalChanges {
+        LocalChanges {
+            modified: vec![LocalFile {
+                path: path.to_string(),
+                mode: 0o644,
+                local: local.as_bytes().to_vec(),
+                base: Some(base.as_bytes().to_vec()),
+            }],
+            added: Vec::new(),
+        }
+    }
+
+    fn updated_tree(path: &str, upstream: &str) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(path), upstream).unwrap();
+        dir
+    }
+
+    fn read(dir: &TempDir, path: &str) -> String {
+        fs::read_to_string(dir.path().join(path)).unwrap()
+    }
+
+    #[test]
+    fn keep_restores_local_content() {
+        let dir = updated_tree("style.css", "a\nB\n");
+        let resolved = changes("style.css", "a\nb\n", "a\nlocal\n")
+            .apply(dir.path(), LocalChangesPolicy::Keep)
+            .unwrap();
+
+        assert_eq!(resolved[0].resolution, Resolution::Kept);
+        assert_eq!(read(&dir, "style.css"), "a\nlocal\n");
+    }
+
+    #[test]
+    fn overwrite_keeps_upstream_and_saves_orig() {
+        let dir = updated_tree("style.css", "a\nB\n");
+        let resolved = changes("style.css", "a\nb\n", "a\nlocal\n")
+            .apply(dir.path(), LocalChangesPolicy::Overwrite)
+            .unwrap();
+
+        assert_eq!(resolved[0].resolution, Resolution::Overwritten);
+        assert_eq!(read(&dir, "style.css"), "a\nB\n");
+        assert_eq!(read(&dir, "style.css.orig"), "a\nlocal\n");
+    }
+
+    #[test]
+    fn merge_combines_non_overlapping_edits() {
+        let dir = updated_tree("style.css", "one\ntwo\nthree\nfour\nUPSTREAM\n");
+        let resolved = changes(
+            "style.css",
+            "one\ntwo\nthree\nfour\nfive\n",
+            "LOCAL\ntwo\nthree\nfour\nfive\n",
+        )
+        .apply(dir.path(), LocalChangesPolicy::Merge)
+        .unwrap();
+
+        assert_eq!(resolved[0].resolution, Resolution::Merged);
+        assert_eq!(
+            read(&dir, "style.css"),
+            "LOCAL\ntwo\nthree\nfour\nUPSTREAM\n"
+        );
+        assert!(dir.path().join("style.css.orig").exists());
+    }
+
+    #[test]
+    fn merge_marks_conflicts() {
+        let dir = updated_tree("style.css", "a\nupstream\n");
+        let resolved = changes("style.css", "a\nb\n", "a\nlocal\n")
+            .apply(dir.path(), LocalChangesPolicy::Merge)
+            .unwrap();
+
+        assert_eq!(resolved[0].resolution, Resolution::Conflicted);
+        let merged = read(&dir, "style.css");
+        assert!(merged.contains("<<<<<<<"));
+        assert!(merged.contains("local") && merged.contains("upstream"));
+        assert_eq!(read(&dir, "style.css.orig"), "a\nlocal\n");
+    }
+
+    #[test]
+    fn added_files_are_restored_when_upstream_lacks_them() {
+        let dir = TempDir::new().unwrap();
+        let changes = LocalChanges {
+            modified: Vec::new(),
+            added: vec![LocalFile {
+                path: "scripts/custom.sh".to_string(),
+                mode: 0o755,
+                local: b"echo mine".to_vec(),
+                base: None,
+            }],
+        };
+        let resolved = changes
+            .apply(dir.path(), LocalChangesPolicy::Overwrite)
+            .unwrap();
+
+        assert_eq!(resolved[0].resolution, Resolution::Restored);
+        let mode = fs::metadata(dir.path().join("scripts/custom.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
@@ -1,18 +1,35 @@
 pub mod dependency_checker;
+pub mod desktop_notification;
+pub mod install_manifest;
+pub mod lifecycle;
+pub mod local_changes;
 pub mod module_installer;
 pub mod omarchy_theme;
 pub mod package_config;
 pub mod paths;
 pub mod preferences;
+pub mod script_review;
 pub mod settings;
+pub mod update_diff;
 pub mod waybar_config;
 
 pub use dependency_checker::{
-    DepCheckError, DepReport, DepResult, DepSpec, DepType, check_binary, check_dependencies,
-    check_python_module, extract_version, is_valid_binary_name, is_valid_python_module_name,
+    DepCheckError, DepReport, DepResult, DepSpec, DepType, PROBE_TIMEOUT, check_binary,
+    check_dependencies, check_dependencies_async, check_python_module, clear_probe_cache,
+    extract_version, is_valid_binary_name, is_valid_python_module_name,
 };
+pub use desktop_notification::notify_desktop;
+pub use install_manifest::{
+    FileRecord, InstallManifest, IntegrityReport, ManifestError, record_install_manifest,
+    verify_module_integrity,
+};
+pub use lifecycle::{
+    LifecycleError, LifecycleHook, append_install_log, module_sandbox_config, run_hook,
+    run_hook_streaming,
+};
+pub use local_changes::{LocalChanges, LocalChangesPolicy, ORIG_SUFFIX, Resolution, ResolvedFile};
 pub use module_installer::{
-    InstallError, InstallParams, InstallResult, InstallStage, SecureInstaller,
+    InstallError, InstallParams, InstallResult, InstallStage, SecureInstaller, run_uninstall_script,
 };
 pub use omarchy_theme::{OmarchyPalette, is_omarchy_available, load_omarchy_palette};
 pub use package_config::{PackageConfigError, PackageInfo, PackageToml, Permissions};
@@ -20,4 +37,8 @@ pub use preferences::{
     ModulePreferences, PreferenceField, PreferenceValue, PreferencesSchema, SelectOption,
     load_preferences, load_schema, save_preferences,
 };
+pub use script_review::{
+    ScriptFinding, collect_module_scripts, exec_script_references, review_module_scripts,
+};
 pub use settings::{UserSettings, load_settings, save_settings};
+pub use update_diff::{DIFFED_FILES, FileChange, FileDiff, ModuleDiff};
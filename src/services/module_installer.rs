@@ -1,10 +1,14 @@
+use crate::domain::PermissionSet;
 use crate::security::{
-    ExtractionError, OfflinePolicy, RevocationError, SCRIPT_TIMEOUT_SECS, ScriptError, Verifier,
-    VerifyError, check_revocation, compute_sha256, extract_tarball_safe, run_script_sandboxed,
+    DEFAULT_MAX_LIST_AGE_HOURS, ExtractionError, OfflinePolicy, OutputStream, RevocationError,
+    RevocationSettings, ScriptError, ScriptResult, SignerIdentity, Verifier, VerifyError,
+    check_revocation, compute_sha256, extract_archive_safe,
+};
+use crate::services::{
+    DepCheckError, DepReport, LifecycleError, LifecycleHook, PackageConfigError, PackageToml,
+    ScriptFinding, check_dependencies_async, review_module_scripts, run_hook, run_hook_streaming,
 };
-use crate::services::{DepReport, PackageConfigError, PackageToml, check_dependencies};
 use std::path::{Path, PathBuf};
-use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -24,12 +28,18 @@ pub enum InstallError {
     #[error("Package config error: {0}")]
     PackageConfig(#[from] PackageConfigError),
 
+    #[error("Dependency check failed: {0}")]
+    DependencyCheck(#[from] DepCheckError),
+
     #[error("Missing required dependencies: {0:?}")]
     MissingDependencies(Vec<String>),
 
     #[error("Script execution failed: {0}")]
     ScriptExecution(#[from] ScriptError),
 
+    #[error("Lifecycle hook failed: {0}")]
+    Lifecycle(#[from] LifecycleError),
+
     #[error("Network error: {0}")]
     Network(String),
 
@@ -46,6 +56,7 @@ pub enum InstallStage {
     VerifyingHash,
     ExtractingPackage,
     CheckingDependencies,
+    InspectingScripts,
     RunningInstallScript,
     Complete,
 }
@@ -61,6 +72,7 @@ impl InstallStage {
             Self::VerifyingHash => "Verifying hash",
             Self::ExtractingPackage => "Extracting package",
             Self::CheckingDependencies => "Checking dependencies",
+            Self::InspectingScripts => "Inspecting scripts",
             Self::RunningInstallScript => "Running install script",
             Self::Complete => "Installation complete",
         }
@@ -79,6 +91,7 @@ pub struct InstallParams<'a> {
 pub struct SecureInstaller {
     verifier: Verifier,
     offline_policy: OfflinePolicy,
+    max_list_age_hours: u32,
 }
 
 impl SecureInstaller {
@@ -87,6 +100,7 @@ impl SecureInstaller {
         Self {
             verifier: Verifier::new(),
             offline_policy: OfflinePolicy::default(),
+            max_list_age_hours: DEFAULT_MAX_LIST_AGE_HOURS,
         }
     }
 
@@ -96,6 +110,13 @@ impl SecureInstaller {
         self
     }
 
+    #[must_use]
+    pub fn with_revocation_settings(mut self, settings: RevocationSettings) -> Self {
+        self.offline_policy = settings.offline_policy;
+        self.max_list_age_hours = settings.max_list_age_hours;
+        self
+    }
+
     pub async fn install<F>(
         &self,
         params: InstallParams<'_>,
@@ -105,11 +126,18 @@ impl SecureInstaller {
         F: Fn(InstallStage),
     {
         progress(InstallStage::RevocationCheck);
-        check_revocation(params.uuid, params.version, self.offline_policy).await?;
+        let revocation = RevocationSettings {
+            offline_policy: self.offline_policy,
+            max_list_age_hours: self.max_list_age_hours,
+            ..RevocationSettings::default()
+        };
+        check_revocation(params.uuid, params.version, revocation).await?;
 
         progress(InstallStage::VerifyingSignature);
-        self.verifier
-            .verify(params.package_data, params.signature)?;
+        let signer = self
+            .verifier
+            .verify_package(params.package_data, params.signature)
+            .await?;
 
         progress(InstallStage::VerifyingHash);
         let actual_hash = compute_sha256(params.package_data);
@@ -122,7 +150,7 @@ impl SecureInstaller {
 
         progress(InstallStage::ExtractingPackage);
         std::fs::create_dir_all(params.dest_dir)?;
-        extract_tarball_safe(params.package_data, params.dest_dir)?;
+        extract_archive_safe(params.package_data, params.dest_dir)?;
 
         let package_toml_path = params.dest_dir.join("Package.toml");
         let package_config = if package_toml_path.exists() {
@@ -133,8 +161,7 @@ impl SecureInstaller {
 
         progress(InstallStage::CheckingDependencies);
         let dep_report = if let Some(ref config) = package_config {
-            let specs = config.to_dep_specs();
-            let report = check_dependencies(&specs);
+            let report = check_dependencies_async(config.to_dep_specs()).await?;
             if !report.all_satisfied {
                 return Err(InstallError::MissingDependencies(
                     report.missing_required.clone(),
@@ -145,38 +172,39 @@ impl SecureInstaller {
             None
         };
 
-        progress(InstallStage::RunningInstallScript);
-        let script_result = if let Some(ref config) = package_config {
-            if let Some(ref script_name) = config.package.install_script {
-                let script_path = params.dest_dir.join(script_name);
-                if script_path.exists() {
-                    let sandbox_config = config.to_sandbox_config();
-                    let timeout = Duration::from_secs(SCRIPT_TIMEOUT_SECS);
-                    Some(run_script_sandboxed(
-                        &script_path,
-                        params.dest_dir,
-                        &sandbox_config,
-                        timeout,
-                    )?)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        progress(InstallStage::Complete);
+        progress(InstallStage::InspectingScripts);
+        let script_review = review_module_scripts(params.dest_dir);
 
         Ok(InstallResult {
             module_dir: params.dest_dir.to_path_buf(),
             dep_report,
-            script_output: script_result.map(|r| r.stdout),
+            script_review,
+            signer,
+            permissions: package_config
+                .as_ref()
+                .map(PackageToml::permission_set)
+                .unwrap_or_default(),
         })
     }
 
+    pub fn finish<F, O>(
+        &self,
+        uuid: &str,
+        module_dir: &Path,
+        progress: F,
+        output: O,
+    ) -> Result<Option<String>, InstallError>
+    where
+        F: Fn(InstallStage),
+        O: FnMut(OutputStream, &str),
+    {
+        progress(InstallStage::RunningInstallScript);
+        let script_result = run_hook_streaming(uuid, module_dir, LifecycleHook::Install, output)?;
+
+        progress(InstallStage::Complete);
+        Ok(script_result.map(|r| r.stdout))
+    }
+
     pub fn verify_only(
         &self,
         package_data: &[u8],
@@ -206,7 +234,16 @@ impl Default for SecureInstaller {
 pub struct InstallResult {
     pub module_dir: PathBuf,
     pub dep_report: Option<DepReport>,
-    pub script_output: Option<String>,
+    pub script_review: Vec<ScriptFinding>,
+    pub signer: SignerIdentity,
+    pub permissions: PermissionSet,
+}
+
+pub fn run_uninstall_script(
+    uuid: &str,
+    module_dir: &Path,
+) -> Result<Option<ScriptResult>, InstallError> {
+    Ok(run_hook(uuid, module_dir, LifecycleHook::Uninstall)?)
 }
 
 #[cfg(test)]
@@ -222,6 +259,7 @@ mod tests {
         assert!(!InstallStage::VerifyingHash.description().is_empty());
         assert!(!InstallStage::ExtractingPackage.description().is_empty());
         assert!(!InstallStage::CheckingDependencies.description().is_empty());
+        assert!(!InstallStage::InspectingScripts.description().is_empty());
         assert!(!InstallStage::RunningInstallScript.description().is_empty());
         assert!(!InstallStage::Complete.description().is_empty());
     }
@@ -238,6 +276,29 @@ mod tests {
         assert_eq!(installer.offline_policy, OfflinePolicy::AllowOffline);
     }
 
+    #[test]
+    fn uninstall_script_skipped_without_package_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = run_uninstall_script("test@test", dir.path()).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn uninstall_script_rejects_path_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Package.toml"),
+            "[package]\nname = \"test\"\nversion = \"1.0.0\"\nuninstall_script = \"../evil.sh\"\n",
+        )
+        .unwrap();
+
+        let result = run_uninstall_script("test@test", dir.path());
+        assert!(matches!(
+            result,
+            Err(InstallError::Lifecycle(LifecycleError::ScriptPath(_)))
+        ));
+    }
+
     #[test]
     fn verify_only_rejects_hash_mismatch() {
         let installer = SecureInstaller::new();
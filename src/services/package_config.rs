@@ -1,4 +1,5 @@
-use crate::security::SandboxConfig;
+use crate::domain::PermissionSet;
+use crate::security::{ResourceLimits, SandboxConfig};
 use crate::services::dependency_checker::{DepSpec, DepType};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -24,6 +25,8 @@ pub struct PackageToml {
     pub dependencies: HashMap<String, DepEntry>,
     #[serde(default)]
     pub permissions: Permissions,
+    #[serde(default)]
+    pub limits: ResourceLimits,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,6 +39,14 @@ pub struct PackageInfo {
     pub install_script: Option<String>,
     #[serde(default)]
     pub uninstall_script: Option<String>,
+    #[serde(default)]
+    pub pre_update: Option<String>,
+    #[serde(default)]
+    pub post_update: Option<String>,
+    #[serde(default)]
+    pub on_enable: Option<String>,
+    #[serde(default)]
+    pub on_disable: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,6 +76,12 @@ pub struct Permissions {
     pub read_paths: Vec<String>,
     #[serde(default)]
     pub write_paths: Vec<String>,
+    #[serde(default)]
+    pub runtime_sockets: Vec<String>,
+    #[serde(default)]
+    pub udp: bool,
+    #[serde(default)]
+    pub env: Vec<String>,
 }
 
 impl std::str::FromStr for PackageToml {
@@ -108,6 +125,22 @@ impl PackageToml {
             .collect()
     }
 
+    pub fn permission_set(&self) -> PermissionSet {
+        let mut dependencies: Vec<String> = self.dependencies.keys().cloned().collect();
+        dependencies.sort();
+        PermissionSet {
+            network: self.permissions.network,
+            udp: self.permissions.udp,
+            ports: self.permissions.ports.clone(),
+            read_paths: self.permissions.read_paths.clone(),
+            write_paths: self.permissions.write_paths.clone(),
+            runtime_sockets: self.permissions.runtime_sockets.clone(),
+            env: self.permissions.env.clone(),
+            dependencies,
+            install_script: self.package.install_script.clone(),
+        }
+    }
+
     pub fn to_sandbox_config(&self) -> SandboxConfig {
         SandboxConfig {
             allow_network: self.permissions.network,
@@ -124,6 +157,11 @@ impl PackageToml {
                 .iter()
                 .map(|s| shellexpand::tilde(s).to_string().into())
                 .collect(),
+            runtime_sockets: self.permissions.runtime_sockets.clone(),
+            allow_udp: self.permissions.udp,
+            env: self.permissions.env.clone(),
+            limits: self.limits,
+            ..Default::default()
         }
     }
 }
@@ -174,6 +212,14 @@ write_paths = ["/tmp/module-cache"]
         );
         assert!(pkg.permissions.network);
         assert_eq!(pkg.permissions.ports, vec![80, 443]);
+
+        let permissions = pkg.permission_set();
+        assert_eq!(
+            permissions.dependencies,
+            vec!["curl", "python3", "requests"]
+        );
+        assert_eq!(permissions.install_script.as_deref(), Some("install.sh"));
+        assert_eq!(permissions.write_paths, vec!["/tmp/module-cache"]);
     }
 
     #[test]
@@ -213,6 +259,12 @@ network = true
 ports = [443]
 read_paths = ["/usr/share"]
 write_paths = ["/tmp"]
+runtime_sockets = ["wayland-1"]
+udp = true
+env = ["WAYLAND_DISPLAY"]
+
+[limits]
+cpu_seconds = 10
 "#;
         let pkg = PackageToml::from_str(toml).unwrap();
         let config = pkg.to_sandbox_config();
@@ -221,6 +273,10 @@ write_paths = ["/tmp"]
         assert_eq!(config.allowed_ports, vec![443]);
         assert_eq!(config.extra_ro_paths.len(), 1);
         assert_eq!(config.extra_rw_paths.len(), 1);
+        assert_eq!(config.runtime_sockets, vec!["wayland-1"]);
+        assert!(config.allow_udp);
+        assert_eq!(config.env, vec!["WAYLAND_DISPLAY"]);
+        assert_eq!(config.limits.cpu_seconds, 10);
     }
 
     #[test]
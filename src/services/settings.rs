@@ -4,6 +4,7 @@ use std::io;
 use std::path::PathBuf;
 
 use crate::app::state::{SortField, SortOrder, ViewMode};
+use crate::security::RevocationSettings;
 use crate::theme::ThemeMode;
 
 fn settings_path() -> PathBuf {
@@ -25,6 +26,10 @@ pub struct UserSettings {
     pub sort_order: SortOrder,
     #[serde(default)]
     pub tray_enabled: bool,
+    #[serde(default)]
+    pub sandbox_runtime: bool,
+    #[serde(default)]
+    pub revocation: RevocationSettings,
 }
 
 pub fn load_settings() -> UserSettings {
@@ -72,6 +77,12 @@ mod tests {
             sort_field: SortField::Downloads,
             sort_order: SortOrder::Descending,
             tray_enabled: true,
+            sandbox_runtime: true,
+            revocation: RevocationSettings {
+                offline_policy: crate::security::OfflinePolicy::AllowOffline,
+                max_list_age_hours: 24,
+                auto_disable_revoked: true,
+            },
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -80,5 +91,12 @@ mod tests {
         assert!(matches!(deserialized.theme_mode, ThemeMode::Dark));
         assert!(matches!(deserialized.view_mode, ViewMode::Table));
         assert!(matches!(deserialized.sort_field, SortField::Downloads));
+        assert_eq!(deserialized.revocation, settings.revocation);
+    }
+
+    #[test]
+    fn test_missing_revocation_settings_use_defaults() {
+        let settings: UserSettings = serde_json::from_str(r#"{"tray_enabled": true}"#).unwrap();
+        assert_eq!(settings.revocation, RevocationSettings::default());
     }
 }
@@ -0,0 +1,183 @@
+use crate::security::validate_extraction_path;
+use crate::services::collect_module_scripts;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+pub const DIFFED_FILES: [&str; 2] = ["config.jsonc", "style.css"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "modified",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    pub path: String,
+    pub change: FileChange,
+    pub additions: usize,
+    pub deletions: usize,
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleDiff {
+    pub files: Vec<FileDiff>,
+}
+
+impl ModuleDiff {
+    #[must_use]
+    pub fn compute(installed_dir: &Path, updated_dir: &Path) -> Self {
+        let mut paths: BTreeSet<String> = DIFFED_FILES.iter().map(|f| f.to_string()).collect();
+        paths.extend(collect_module_scripts(installed_dir));
+        paths.extend(collect_module_scripts(updated_dir));
+
+        let files = paths
+            .into_iter()
+            .filter_map(|path| {
+                let old = read_file(installed_dir, &path);
+                let new = read_file(updated_dir, &path);
+                diff_file(path, old.as_deref(), new.as_deref())
+            })
+            .collect();
+        Self { files }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl fmt::Display for ModuleDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in &self.files {
+            match &file.patch {
+                Some(patch) => write!(f, "{patch}")?,
+                None => writeln!(f, "Binary file {} {}", file.path, file.change)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_file(root: &Path, relative: &str) -> Option<Vec<u8>> {
+    let path = validate_extraction_path(root, Path::new(relative)).ok()?;
+    std::fs::read(path).ok()
+}
+
+fn diff_file(path: String, old: Option<&[u8]>, new: Option<&[u8]>) -> Option<FileDiff> {
+    let change = match (old, new) {
+        (None, None) => return None,
+        (Some(old), Some(new)) if old == new => return None,
+        (None, Some(_)) => FileChange::Added,
+        (Some(_), None) => FileChange::Removed,
+        (Some(_), Some(_)) => FileChange::Modified,
+    };
+
+    let (Some(old), Some(new)) = (as_text(old), as_text(new)) else {
+        return Some(FileDiff {
+            path,
+            change,
+            additions: 0,
+            deletions: 0,
+            patch: None,
+        });
+    };
+
+    let patch = diffy::DiffOptions::new()
+        .set_original_filename(format!("a/{path}"))
+        .set_modified_filename(format!("b/{path}"))
+        .create_patch(old, new);
+    let lines = patch.hunks().iter().flat_map(|hunk| hunk.lines());
+    let (additions, deletions) = lines.fold((0, 0), |(add, del), line| match line {
+        diffy::Line::Insert(_) => (add + 1, del),
+        diffy::Line::Delete(_) => (add, del + 1),
+        diffy::Line::Context(_) => (add, del),
+    });
+
+    Some(FileDiff {
+        path,
+        change,
+        additions,
+        deletions,
+        patch: Some(patch.to_string()),
+    })
+}
+
+fn as_text(content: Option<&[u8]>) -> Option<&str> {
+    match content {
+        Some(bytes) => std::str::from_utf8(bytes).ok(),
+        None => Some(""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn tree(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files {
+            let full = dir.path().join(path);
+            fs::create_dir_all(full.parent().unwrap()).unwrap();
+            fs::write(full, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn reports_line_changes_in_reviewed_files() {
+        let installed = tree(&[("style.css", "a\nb\n"), ("README.md", "old")]);
+        let updated = tree(&[("style.css", "a\nc\n"), ("README.md", "new")]);
+
+        let diff = ModuleDiff::compute(installed.path(), updated.path());
+
+        assert_eq!(diff.files.len(), 1);
+        let file = &diff.files[0];
+        assert_eq!(file.path, "style.css");
+        assert_eq!(file.change, FileChange::Modified);
+        assert_eq!((file.additions, file.deletions), (1, 1));
+        let patch = file.patch.as_deref().unwrap();
+        assert!(patch.contains("--- a/style.css"));
+        assert!(patch.contains("-b\n+c\n"));
+    }
+
+    #[test]
+    fn includes_added_scripts_referenced_by_config() {
+        let installed = tree(&[("config.jsonc", "{}")]);
+        let config = r#"{"exec": "$MODULE_PATH/scripts/run.sh"}"#;
+        let updated = tree(&[("config.jsonc", config), ("scripts/run.sh", "echo hi\n")]);
+
+        let diff = ModuleDiff::compute(installed.path(), updated.path());
+        let changes: Vec<(&str, FileChange)> = diff
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.change))
+            .collect();
+
+        assert!(changes.contains(&("config.jsonc", FileChange::Modified)));
+        assert!(changes.contains(&("scripts/run.sh", FileChange::Added)));
+    }
+
+    #[test]
+    fn identical_trees_have_no_diff() {
+        let installed = tree(&[("style.css", "a\n")]);
+        let updated = tree(&[("style.css", "a\n")]);
+        assert!(ModuleDiff::compute(installed.path(), updated.path()).is_empty());
+    }
+}
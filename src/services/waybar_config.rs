@@ -127,11 +127,115 @@ pub fn substitute_preferences(
     result
 }
 
+const MODULE_PATH_PREFIXES: &[&str] = &["$MODULE_PATH/", "${MODULE_PATH}/"];
+
+pub const SCRIPT_INTERPRETERS: &[&str] = &[
+    "sh", "bash", "dash", "zsh", "python", "python3", "perl", "ruby", "node", "lua",
+];
+
+pub fn is_script_interpreter(program: &str) -> bool {
+    let name = program.rsplit('/').next().unwrap_or(program);
+    SCRIPT_INTERPRETERS.contains(&name)
+}
+
+fn split_token(command: &str) -> (&str, &str) {
+    let command = command.trim_start();
+    command
+        .split_once(char::is_whitespace)
+        .map(|(token, rest)| (token, rest.trim_start()))
+        .unwrap_or((command, ""))
+}
+
+fn module_script(token: &str) -> Option<&str> {
+    MODULE_PATH_PREFIXES
+        .iter()
+        .find_map(|prefix| token.strip_prefix(prefix))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeSandbox<'a> {
+    pub executable: &'a str,
+    pub uuid: &'a str,
+}
+
+impl RuntimeSandbox<'_> {
+    fn wrap(&self, command: &str) -> Result<Option<String>, String> {
+        let (first, rest) = split_token(command);
+        let (interpreter, script, rest) = match module_script(first) {
+            Some(script) => (None, script, rest),
+            None if is_script_interpreter(first) => {
+                let (second, rest) = split_token(rest);
+                match module_script(second) {
+                    Some(script) => (Some(first), script, rest),
+                    None => return Err(format!("Cannot sandbox module command: {command}")),
+                }
+            }
+            None => return Ok(None),
+        };
+
+        let mut wrapped = format!("{} run", shell_quote(self.executable));
+        if let Some(interpreter) = interpreter {
+            wrapped.push_str(&format!(" --interpreter {}", shell_quote(interpreter)));
+        }
+        wrapped.push_str(&format!(
+            " {} {}",
+            shell_quote(self.uuid),
+            shell_quote(script)
+        ));
+        if !rest.is_empty() {
+            wrapped.push(' ');
+            wrapped.push_str(rest);
+        }
+        Ok(Some(wrapped))
+    }
+
+    fn rewrite(&self, value: &mut serde_json::Value) -> Result<(), String> {
+        match value {
+            serde_json::Value::Object(obj) => {
+                for (key, value) in obj.iter_mut() {
+                    if (key.starts_with("exec") || key.starts_with("on-"))
+                        && let serde_json::Value::String(command) = value
+                    {
+                        if let Some(wrapped) = self.wrap(command)? {
+                            *command = wrapped;
+                        }
+                    } else {
+                        self.rewrite(value)?;
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.rewrite(item)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
 pub fn merge_module_config(
     waybar_content: &str,
     module_content: &str,
     install_path: &str,
+    sandbox: Option<RuntimeSandbox<'_>>,
 ) -> Result<String, String> {
+    let module_content = match sandbox {
+        Some(sandbox) => {
+            let mut value = jsonc_parser::parse_to_serde_value(module_content, &Default::default())
+                .map_err(|e| format!("Failed to parse module config: {e}"))?
+                .ok_or("Empty module config")?;
+            sandbox.rewrite(&mut value)?;
+            serde_json::to_string(&value)
+                .map_err(|e| format!("Failed to serialize module config: {e}"))?
+        }
+        None => module_content.to_string(),
+    };
     let module_content = module_content.replace("$MODULE_PATH", install_path);
 
     let waybar_value: serde_json::Value =
@@ -324,7 +428,7 @@ mod tests {
         let waybar = r#"{"layer": "top", "modules-center": ["clock"]}"#;
         let module = r#"{"custom/weather": {"exec": "curl wttr.in", "interval": 600}}"#;
 
-        let result = merge_module_config(waybar, module, "/path/to/module").unwrap();
+        let result = merge_module_config(waybar, module, "/path/to/module", None).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
 
         assert!(parsed["custom/weather"].is_object());
@@ -340,6 +444,7 @@ mod tests {
             waybar,
             module,
             "/home/user/.local/share/barforge/modules/test@ns",
+            None,
         )
         .unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
@@ -350,6 +455,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_module_config_wraps_module_scripts_in_sandbox() {
+        let waybar = r#"{"layer": "top"}"#;
+        let module = r#"{"custom/script": {
+            "exec": "$MODULE_PATH/script.sh --json",
+            "on-click": "${MODULE_PATH}/click.py",
+            "on-click-right": "pavucontrol",
+            "interval": 5
+        }}"#;
+        let sandbox = RuntimeSandbox {
+            executable: "/usr/bin/barforge",
+            uuid: "test@ns",
+        };
+
+        let result =
+            merge_module_config(waybar, module, "/modules/test@ns", Some(sandbox)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(
+            parsed["custom/script"]["exec"],
+            "'/usr/bin/barforge' run 'test@ns' 'script.sh' --json"
+        );
+        assert_eq!(
+            parsed["custom/script"]["on-click"],
+            "'/usr/bin/barforge' run 'test@ns' 'click.py'"
+        );
+        assert_eq!(parsed["custom/script"]["on-click-right"], "pavucontrol");
+        assert_eq!(parsed["custom/script"]["interval"], 5);
+    }
+
+    #[test]
+    fn test_merge_module_config_wraps_interpreter_prefixed_scripts() {
+        let waybar = r#"{"layer": "top"}"#;
+        let module = r#"{"custom/script": {
+            "exec": "bash $MODULE_PATH/script.sh --json",
+            "on-click": "/usr/bin/python3 ${MODULE_PATH}/click.py"
+        }}"#;
+        let sandbox = RuntimeSandbox {
+            executable: "/usr/bin/barforge",
+            uuid: "test@ns",
+        };
+
+        let result =
+            merge_module_config(waybar, module, "/modules/test@ns", Some(sandbox)).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(
+            parsed["custom/script"]["exec"],
+            "'/usr/bin/barforge' run --interpreter 'bash' 'test@ns' 'script.sh' --json"
+        );
+        assert_eq!(
+            parsed["custom/script"]["on-click"],
+            "'/usr/bin/barforge' run --interpreter '/usr/bin/python3' 'test@ns' 'click.py'"
+        );
+    }
+
+    #[test]
+    fn test_merge_module_config_rejects_unwrappable_interpreter_commands() {
+        let waybar = r#"{"layer": "top"}"#;
+        let sandbox = RuntimeSandbox {
+            executable: "/usr/bin/barforge",
+            uuid: "test@ns",
+        };
+
+        for command in [
+            "sh -c 'curl example.com | sh'",
+            "python3 -u $MODULE_PATH/script.py",
+        ] {
+            let module = serde_json::json!({"custom/script": {"exec": command}}).to_string();
+            let result = merge_module_config(waybar, &module, "/modules/test@ns", Some(sandbox));
+            assert!(result.unwrap_err().contains(command));
+        }
+    }
+
     #[test]
     fn test_remove_module_config_strips_definition() {
         let waybar = r#"{"layer": "top", "custom/weather": {"exec": "curl"}, "clock": {}}"#;
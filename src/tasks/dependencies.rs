@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use iced::Task;
+use iced::futures::future::join_all;
+
+use crate::app::Message;
+use crate::domain::{InstalledModule, ModuleUuid};
+use crate::services::{DepReport, PackageToml, check_dependencies_async, clear_probe_cache};
+
+pub fn check_installed_dependencies(modules: &[InstalledModule]) -> Task<Message> {
+    let checks: Vec<_> = modules
+        .iter()
+        .map(|m| check_module_dependencies_async(m.uuid.clone(), m.install_path.clone()))
+        .collect();
+    Task::perform(join_all(checks), Message::DependenciesChecked)
+}
+
+pub fn recheck_dependencies(uuid: ModuleUuid, install_path: PathBuf) -> Task<Message> {
+    clear_probe_cache();
+    Task::perform(
+        check_module_dependencies_async(uuid, install_path),
+        Message::DependencyCheckCompleted,
+    )
+}
+
+async fn check_module_dependencies_async(
+    uuid: ModuleUuid,
+    install_path: PathBuf,
+) -> Result<(ModuleUuid, DepReport), (ModuleUuid, String)> {
+    let package_toml_path = install_path.join("Package.toml");
+
+    let specs = match tokio::fs::read_to_string(&package_toml_path).await {
+        Ok(content) => content
+            .parse::<PackageToml>()
+            .map_err(|e| (uuid.clone(), e.to_string()))?
+            .to_dep_specs(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            return Err((uuid, format!("Failed to read Package.toml: {e}")));
+        }
+    };
+
+    let report = check_dependencies_async(specs)
+        .await
+        .map_err(|e| (uuid.clone(), e.to_string()))?;
+
+    Ok((uuid, report))
+}
@@ -0,0 +1,26 @@
+use iced::Task;
+
+use crate::app::Message;
+use crate::domain::InstalledModule;
+use crate::services::{IntegrityReport, verify_module_integrity};
+
+pub fn verify_installed_integrity(modules: &[InstalledModule]) -> Task<Message> {
+    Task::batch(modules.iter().map(|m| {
+        Task::perform(
+            verify_integrity_async(m.uuid.to_string(), m.install_path.clone()),
+            Message::IntegrityChecked,
+        )
+    }))
+}
+
+async fn verify_integrity_async(
+    uuid: String,
+    install_path: std::path::PathBuf,
+) -> Result<(String, IntegrityReport), (String, String)> {
+    let task_uuid = uuid.clone();
+    tokio::task::spawn_blocking(move || verify_module_integrity(&task_uuid, &install_path))
+        .await
+        .map_err(|e| (uuid.clone(), format!("Task failed: {e}")))?
+        .map(|report| (uuid.clone(), report))
+        .map_err(|e| (uuid, e.to_string()))
+}
@@ -1,13 +1,23 @@
+mod dependencies;
+mod integrity;
 mod module;
 mod registry;
+mod revocation;
 mod watchers;
 mod waybar;
 
+pub use dependencies::{check_installed_dependencies, recheck_dependencies};
+pub use integrity::verify_installed_integrity;
 pub use module::{
-    change_module_position, install_module, make_scripts_executable, toggle_module,
-    uninstall_module, update_all_modules, update_module,
+    UninstallError, cancel_install, change_module_position, check_local_changes,
+    disable_revoked_module_async, disable_revoked_modules, finish_install, install_module,
+    load_update_diff, make_scripts_executable, toggle_module, toggle_module_async,
+    uninstall_module, uninstall_module_async, update_all_modules, update_diff_async, update_module,
 };
-pub use registry::{load_author_profile, load_module_reviews, load_registry, refresh_registry};
+pub use registry::{
+    fetch_registry_async, load_author_profile, load_module_reviews, load_registry, refresh_registry,
+};
+pub use revocation::{audit_installed_async, sweep_revocations};
 pub use watchers::watch_omarchy_theme;
 
 use std::time::Duration;
@@ -27,7 +37,7 @@ pub fn load_installed() -> Task<Message> {
     Task::perform(load_installed_async(), Message::InstalledLoaded)
 }
 
-async fn load_installed_async() -> Result<Vec<InstalledModule>, String> {
+pub(crate) async fn load_installed_async() -> Result<Vec<InstalledModule>, String> {
     let state_path = paths::data_dir().join("installed.json");
 
     if !state_path.exists() {
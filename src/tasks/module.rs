@@ -1,19 +1,34 @@
-use std::io::Cursor;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
-use flate2::read::GzDecoder;
 use iced::Task;
+use iced::futures::SinkExt;
+use iced::futures::channel::mpsc;
+use iced::futures::executor::block_on;
 use once_cell::sync::Lazy;
-use tar::Archive;
+use thiserror::Error;
 
 use crate::app::Message;
-use crate::domain::{BarSection, InstalledModule, ModuleVersion};
-use crate::security::{parse_github_url_safe, validate_extraction_path};
+use crate::app::state::{
+    AuthorKeyChange, BatchUpdateReport, PendingInstall, PendingUpdate, PermissionEscalation,
+};
+use crate::domain::{BarSection, InstalledModule, ModuleVersion, PermissionSet};
+use crate::security::{
+    ArchiveExtractor, AuthorKeyPins, AuthorKeyStatus, parse_github_url_safe, refresh_key_rotation,
+    signature_key_id,
+};
 use crate::services::paths::{self, HTTP_CLIENT};
-use crate::services::{InstallParams, SecureInstaller};
-
+use crate::services::waybar_config::RuntimeSandbox;
+use crate::services::{
+    InstallParams, InstallResult, LifecycleHook, LocalChanges, LocalChangesPolicy, ModuleDiff,
+    PackageToml, Resolution, ResolvedFile, ScriptFinding, SecureInstaller, record_install_manifest,
+    review_module_scripts, run_hook, run_uninstall_script,
+};
+
+use super::registry::fetch_author_profile_async;
 use super::waybar::{handle_css_injection, handle_css_removal};
 
+const INSTALL_OUTPUT_BUFFER: usize = 64;
+
 static DEFAULT_VERSION: Lazy<ModuleVersion> = Lazy::new(|| {
     ModuleVersion::try_from("1.0.0")
         .unwrap_or_else(|_| unreachable!("1.0.0 is always valid semver"))
@@ -23,8 +38,24 @@ pub fn toggle_module(uuid: String, enabled: bool) -> Task<Message> {
     Task::perform(toggle_module_async(uuid, enabled), Message::ToggleCompleted)
 }
 
-pub fn uninstall_module(uuid: String) -> Task<Message> {
-    Task::perform(uninstall_module_async(uuid), Message::UninstallCompleted)
+pub fn disable_revoked_modules(uuids: Vec<String>) -> Task<Message> {
+    Task::perform(
+        async move {
+            let mut results = Vec::with_capacity(uuids.len());
+            for uuid in uuids {
+                results.push(disable_revoked_module_async(uuid).await);
+            }
+            results
+        },
+        Message::RevokedModulesDisabled,
+    )
+}
+
+pub fn uninstall_module(uuid: String, force: bool) -> Task<Message> {
+    Task::perform(
+        uninstall_module_async(uuid, force),
+        Message::UninstallCompleted,
+    )
 }
 
 pub fn change_module_position(uuid: String, section: BarSection) -> Task<Message> {
@@ -34,14 +65,46 @@ pub fn change_module_position(uuid: String, section: BarSection) -> Task<Message
     )
 }
 
-pub fn update_module(uuid: String, repo_url: String, new_version: ModuleVersion) -> Task<Message> {
+pub fn update_module(
+    update: PendingUpdate,
+    policy: LocalChangesPolicy,
+    approved: Option<PermissionSet>,
+    approved_key: Option<AuthorKeyStatus>,
+) -> Task<Message> {
     Task::perform(
-        update_module_async(uuid, repo_url, new_version),
-        Message::UpdateCompleted,
+        update_module_async(update, policy, approved, approved_key),
+        |result| match result {
+            Ok(UpdateOutcome::Updated(module, resolved)) => {
+                Message::UpdateCompleted(Ok((module, resolved)))
+            }
+            Ok(UpdateOutcome::Blocked(escalation)) => Message::UpdateBlocked(escalation),
+            Ok(UpdateOutcome::KeyChanged(change)) => Message::UpdateKeyChanged(change),
+            Err(e) => Message::UpdateCompleted(Err(e)),
+        },
     )
 }
 
-pub fn update_all_modules(updates: Vec<(String, String, ModuleVersion)>) -> Task<Message> {
+pub fn check_local_changes(uuid: String) -> Task<Message> {
+    Task::perform(
+        check_local_changes_async(uuid),
+        Message::LocalChangesChecked,
+    )
+}
+
+async fn check_local_changes_async(
+    uuid: String,
+) -> Result<(String, Vec<String>), (String, String)> {
+    let module_dir = paths::module_install_path(&uuid);
+    let task_uuid = uuid.clone();
+    let changes =
+        tokio::task::spawn_blocking(move || LocalChanges::capture(&task_uuid, &module_dir))
+            .await
+            .map_err(|e| (uuid.clone(), format!("Local changes task failed: {e}")))?
+            .map_err(|e| (uuid.clone(), format!("Failed to check local changes: {e}")))?;
+    Ok((uuid, changes.paths()))
+}
+
+pub fn update_all_modules(updates: Vec<PendingUpdate>) -> Task<Message> {
     Task::perform(
         update_all_modules_async(updates),
         Message::UpdateAllCompleted,
@@ -51,36 +114,172 @@ pub fn update_all_modules(updates: Vec<(String, String, ModuleVersion)>) -> Task
 pub fn install_module(
     uuid: String,
     name: String,
+    author: String,
     version: Option<ModuleVersion>,
     repo_url: String,
     checksum: Option<String>,
 ) -> Task<Message> {
     Task::perform(
-        install_module_async(uuid, name, version, repo_url, checksum),
+        install_module_async(uuid, name, author, version, repo_url, checksum),
+        |result| match result {
+            Ok(pending) => Message::InstallReviewRequired(pending),
+            Err(e) => Message::InstallCompleted(Err(e)),
+        },
+    )
+}
+
+pub fn finish_install(pending: PendingInstall) -> Task<Message> {
+    with_install_output(
+        pending.uuid.clone(),
+        |output| finish_install_async(pending, output),
         Message::InstallCompleted,
     )
 }
 
+struct InstallOutput {
+    uuid: String,
+    sender: mpsc::Sender<Message>,
+}
+
+impl InstallOutput {
+    fn send_line(&mut self, line: &str) {
+        let message = Message::InstallOutput {
+            uuid: self.uuid.clone(),
+            line: line.to_string(),
+        };
+        let _ = block_on(self.sender.send(message));
+    }
+}
+
+fn with_install_output<T, Fut>(
+    uuid: String,
+    run: impl FnOnce(InstallOutput) -> Fut + Send + 'static,
+    done: impl FnOnce(T) -> Message + Send + 'static,
+) -> Task<Message>
+where
+    Fut: Future<Output = T> + Send,
+{
+    Task::run(
+        iced::stream::channel(INSTALL_OUTPUT_BUFFER, async move |mut sender| {
+            let output = InstallOutput {
+                uuid,
+                sender: sender.clone(),
+            };
+            let result = run(output).await;
+            let _ = sender.send(done(result)).await;
+        }),
+        std::convert::identity,
+    )
+}
+
+pub fn cancel_install(pending: PendingInstall) -> Task<Message> {
+    Task::perform(cancel_install_async(pending), Message::InstallCancelled)
+}
+
 async fn install_module_async(
     uuid: String,
     name: String,
+    author: String,
     version: Option<ModuleVersion>,
     repo_url: String,
     checksum: Option<String>,
-) -> Result<InstalledModule, String> {
-    let install_path = paths::module_install_path(&uuid);
+) -> Result<PendingInstall, String> {
+    let staging_dir = paths::module_staging_dir(&uuid);
     let version = version.unwrap_or_else(|| DEFAULT_VERSION.clone());
 
-    if let Some(expected_hash) = checksum {
-        install_secure(&uuid, &version.to_string(), &expected_hash, &install_path).await?;
-    } else {
-        tokio::fs::create_dir_all(&install_path)
+    let StagedRelease {
+        findings,
+        permissions,
+        signed_by,
+        author_key,
+    } = stage_release(
+        &uuid,
+        &author,
+        &version,
+        &repo_url,
+        checksum.as_deref(),
+        &staging_dir,
+    )
+    .await?;
+
+    tracing::info!("Install of {uuid} paused for permission review");
+    Ok(PendingInstall {
+        uuid,
+        name,
+        version,
+        staging_dir,
+        findings,
+        signed_by,
+        permissions,
+        author,
+        author_key,
+    })
+}
+
+async fn finish_install_async(
+    pending: PendingInstall,
+    mut output: InstallOutput,
+) -> Result<InstalledModule, String> {
+    let PendingInstall {
+        uuid,
+        name,
+        version,
+        staging_dir,
+        signed_by,
+        permissions,
+        author,
+        author_key,
+        ..
+    } = pending;
+
+    let install_path = paths::module_install_path(&uuid);
+    let previous_path = install_path.with_file_name(format!(".{uuid}.previous"));
+    if previous_path.exists() {
+        tokio::fs::remove_dir_all(&previous_path)
+            .await
+            .map_err(|e| format!("Failed to clear previous install: {e}"))?;
+    }
+    let had_previous = install_path.exists();
+    if had_previous {
+        tokio::fs::rename(&install_path, &previous_path)
             .await
-            .map_err(|e| format!("Failed to create install directory: {e}"))?;
+            .map_err(|e| format!("Failed to move existing install aside: {e}"))?;
+    } else if let Some(parent) = install_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create modules directory: {e}"))?;
+    }
+    if let Err(e) = tokio::fs::rename(&staging_dir, &install_path).await {
+        restore_previous_install(&install_path, &previous_path, had_previous).await;
+        return Err(format!("Failed to move module files into place: {e}"));
+    }
 
-        download_module_files(&repo_url, &install_path).await?;
-        make_scripts_executable(&install_path).await?;
+    let hook_uuid = uuid.clone();
+    let module_dir = install_path.clone();
+    let script_result = tokio::task::spawn_blocking(move || {
+        SecureInstaller::new().finish(
+            &hook_uuid,
+            &module_dir,
+            |stage| tracing::debug!("Install stage: {}", stage.description()),
+            |_, line| output.send_line(line),
+        )
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))?;
+
+    if let Err(e) = script_result {
+        if let Err(cleanup) = tokio::fs::remove_dir_all(&install_path).await {
+            tracing::warn!("Failed to clean up {}: {cleanup}", install_path.display());
+        }
+        restore_previous_install(&install_path, &previous_path, had_previous).await;
+        return Err(format!("Install script failed: {e}"));
     }
+    if had_previous && let Err(e) = tokio::fs::remove_dir_all(&previous_path).await {
+        tracing::warn!("Failed to remove {}: {e}", previous_path.display());
+    }
+
+    record_manifest_async(&uuid, &install_path).await;
+    pin_author_key(&author, &author_key);
 
     let has_preferences = install_path.join("preferences.schema.json").exists();
     let waybar_module_name = format!("custom/{}", name.replace(' ', "-").to_lowercase());
@@ -96,6 +295,8 @@ async fn install_module_async(
         installed_at: chrono::Utc::now(),
         registry_version: Some(version),
         position: None,
+        signed_by,
+        approved_permissions: Some(permissions),
     };
 
     let state_path = paths::data_dir().join("installed.json");
@@ -108,6 +309,7 @@ async fn install_module_async(
         Vec::new()
     };
 
+    modules.retain(|m| m.uuid != installed.uuid);
     modules.push(installed.clone());
 
     let content = serde_json::to_string_pretty(&modules)
@@ -127,12 +329,40 @@ async fn install_module_async(
     Ok(installed)
 }
 
+async fn restore_previous_install(install_path: &Path, previous_path: &Path, had_previous: bool) {
+    if !had_previous {
+        return;
+    }
+    if let Err(e) = tokio::fs::rename(previous_path, install_path).await {
+        tracing::warn!(
+            "Failed to restore previous install from {}: {e}",
+            previous_path.display()
+        );
+    }
+}
+
+async fn cancel_install_async(pending: PendingInstall) -> Result<String, String> {
+    match tokio::fs::remove_dir_all(&pending.staging_dir).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("Failed to remove extracted files: {e}")),
+    }
+
+    tracing::info!("Install of {} cancelled during review", pending.uuid);
+    Ok(pending.uuid)
+}
+
 async fn install_secure(
     uuid: &str,
+    author: &str,
     version: &str,
     expected_hash: &str,
     dest_dir: &Path,
-) -> Result<(), String> {
+) -> Result<(InstallResult, AuthorKeyStatus), String> {
+    if let Err(e) = refresh_key_rotation().await {
+        tracing::warn!("Using cached signing keys: {e}");
+    }
+
     let package_url = paths::package_url(uuid, version);
     let signature_url = paths::signature_url(uuid, version);
 
@@ -156,7 +386,10 @@ async fn install_secure(
         .await
         .map_err(|e| format!("Failed to read package: {e}"))?;
 
-    let installer = SecureInstaller::new();
+    let author_key = check_author_key(uuid, author, version, &package_data).await?;
+
+    let installer = SecureInstaller::new()
+        .with_revocation_settings(crate::services::load_settings().revocation);
     let params = InstallParams {
         uuid,
         version,
@@ -165,23 +398,94 @@ async fn install_secure(
         expected_hash,
         dest_dir,
     };
-    installer
+    let result = installer
         .install(params, |stage| {
             tracing::debug!("Install stage: {}", stage.description());
         })
         .await
         .map_err(|e| format!("Secure installation failed: {e}"))?;
 
-    Ok(())
+    tracing::info!("{uuid} {version} signed by {}", result.signer);
+    Ok((result, author_key))
+}
+
+async fn check_author_key(
+    uuid: &str,
+    author: &str,
+    version: &str,
+    package_data: &[u8],
+) -> Result<AuthorKeyStatus, String> {
+    let co_signature = match HTTP_CLIENT
+        .get(paths::author_signature_url(uuid, version))
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+    {
+        Ok(response) => Some(
+            response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read author signature: {e}"))?,
+        ),
+        Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => None,
+        Err(e) => return Err(format!("Failed to fetch author signature: {e}")),
+    };
+
+    let pins = AuthorKeyPins::load();
+    let pinned_key_id = pins.get(author).map(|pin| pin.key_id.clone());
+    let profile_key = match &co_signature {
+        Some(signature) if signature_key_id(signature) != pinned_key_id => {
+            fetch_author_profile_async(author.to_string())
+                .await?
+                .author
+                .signing_key
+        }
+        _ => None,
+    };
+
+    let status = pins
+        .check(
+            author,
+            package_data,
+            co_signature.as_deref(),
+            profile_key.as_deref(),
+        )
+        .map_err(|e| format!("Author signature verification failed: {e}"))?;
+    if let Some(warning) = status.warning(author) {
+        tracing::warn!("{uuid} {version}: {warning}");
+    }
+    Ok(status)
+}
+
+fn pin_author_key(author: &str, status: &AuthorKeyStatus) {
+    let Some(pin) = status.pin_candidate() else {
+        return;
+    };
+    let mut pins = AuthorKeyPins::load();
+    pins.pin(author, pin.clone());
+    match pins.save() {
+        Ok(()) => tracing::info!("Pinned author key {} for {author}", pin.key_id),
+        Err(e) => tracing::warn!("Failed to pin author key for {author}: {e}"),
+    }
+}
+
+enum UpdateOutcome {
+    Updated(InstalledModule, Vec<ResolvedFile>),
+    Blocked(PermissionEscalation),
+    KeyChanged(AuthorKeyChange),
 }
 
 async fn update_module_async(
-    uuid: String,
-    repo_url: String,
-    new_version: ModuleVersion,
-) -> Result<InstalledModule, String> {
+    update: PendingUpdate,
+    policy: LocalChangesPolicy,
+    approved: Option<PermissionSet>,
+    approved_key: Option<AuthorKeyStatus>,
+) -> Result<UpdateOutcome, String> {
     use crate::services::waybar_config;
 
+    let uuid = update.uuid.clone();
+    let new_version = update.version.clone();
+
     let install_path = paths::module_install_path(&uuid);
     let state_path = paths::data_dir().join("installed.json");
 
@@ -205,7 +509,68 @@ async fn update_module_async(
         .map(|p| p.section)
         .unwrap_or(BarSection::Center);
 
+    let staging_dir = paths::module_staging_dir(&uuid);
+    let StagedRelease {
+        permissions: requested,
+        signed_by,
+        author_key,
+        ..
+    } = stage_release(
+        &update.uuid,
+        &update.author,
+        &update.version,
+        &update.repo_url,
+        update.checksum.as_deref(),
+        &staging_dir,
+    )
+    .await?;
+
+    if author_key.needs_confirmation(approved_key.as_ref()) {
+        if let Err(e) = tokio::fs::remove_dir_all(&staging_dir).await {
+            tracing::warn!("Failed to remove staged update for {uuid}: {e}");
+        }
+        tracing::info!("Update of {uuid} blocked pending author key confirmation");
+        return Ok(UpdateOutcome::KeyChanged(AuthorKeyChange {
+            uuid,
+            name: waybar_module_name,
+            version: new_version,
+            author: update.author,
+            status: author_key,
+        }));
+    }
+
+    let added =
+        requested.escalations_from(&module.approved_permissions.clone().unwrap_or_default());
+    if !added.is_empty() && approved.as_ref() != Some(&requested) {
+        if let Err(e) = tokio::fs::remove_dir_all(&staging_dir).await {
+            tracing::warn!("Failed to remove staged update for {uuid}: {e}");
+        }
+        tracing::info!("Update of {uuid} blocked pending permission approval");
+        return Ok(UpdateOutcome::Blocked(PermissionEscalation {
+            uuid,
+            name: waybar_module_name,
+            version: new_version,
+            requested,
+            added,
+        }));
+    }
+
+    let local_changes = {
+        let uuid = uuid.clone();
+        let module_dir = install_path.clone();
+        tokio::task::spawn_blocking(move || LocalChanges::capture(&uuid, &module_dir))
+            .await
+            .map_err(|e| format!("Local changes task failed: {e}"))?
+            .map_err(|e| format!("Failed to snapshot local changes: {e}"))?
+    };
+
+    run_hook_async(&uuid, &install_path, LifecycleHook::PreUpdate).await?;
+
     if was_enabled {
+        if let Err(e) = run_hook_async(&uuid, &install_path, LifecycleHook::Disable).await {
+            tracing::warn!("{e}");
+        }
+
         handle_css_removal(&uuid).await;
 
         if let Ok(waybar_content) = waybar_config::load_config().await {
@@ -230,11 +595,25 @@ async fn update_module_async(
             .map_err(|e| format!("Failed to remove old module files: {e}"))?;
     }
 
-    tokio::fs::create_dir_all(&install_path)
+    tokio::fs::rename(&staging_dir, &install_path)
         .await
-        .map_err(|e| format!("Failed to create install directory: {e}"))?;
+        .map_err(|e| format!("Failed to move updated module files into place: {e}"))?;
 
-    download_module_files(&repo_url, &install_path).await?;
+    record_manifest_async(&uuid, &install_path).await;
+
+    if let Err(e) = run_hook_async(&uuid, &install_path, LifecycleHook::PostUpdate).await {
+        tracing::warn!("{e}");
+    }
+
+    let resolved = if local_changes.is_empty() {
+        Vec::new()
+    } else {
+        let module_dir = install_path.clone();
+        tokio::task::spawn_blocking(move || local_changes.apply(&module_dir, policy))
+            .await
+            .map_err(|e| format!("Local changes task failed: {e}"))?
+            .map_err(|e| format!("Failed to reapply local changes: {e}"))?
+    };
 
     let has_preferences = install_path.join("preferences.schema.json").exists();
 
@@ -245,7 +624,9 @@ async fn update_module_async(
 
     module.version = new_version.clone();
     module.registry_version = Some(new_version);
+    module.signed_by = signed_by;
     module.has_preferences = has_preferences;
+    module.approved_permissions = Some(requested);
 
     let updated = module.clone();
 
@@ -256,30 +637,16 @@ async fn update_module_async(
         .await
         .map_err(|e| format!("Failed to save state: {e}"))?;
 
+    pin_author_key(&update.author, &author_key);
+
     if was_enabled {
         if let Ok(waybar_content) = waybar_config::load_config().await {
-            let config_path = install_path.join("config.jsonc");
-            let with_module_config = if config_path.exists() {
-                if let Ok(module_config) = tokio::fs::read_to_string(&config_path).await {
-                    let prefs = crate::services::preferences::load_preferences(&uuid);
-                    let module_config =
-                        waybar_config::substitute_preferences(&module_config, &prefs);
-                    let install_path_str = install_path.to_string_lossy();
-                    waybar_config::merge_module_config(
-                        &waybar_content,
-                        &module_config,
-                        &install_path_str,
-                    )
-                    .unwrap_or_else(|e| {
-                        tracing::warn!("Failed to merge module config during update: {e}");
-                        waybar_content.clone()
-                    })
-                } else {
+            let with_module_config = merge_module_definition(&uuid, &install_path, &waybar_content)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to merge module config during update: {e}");
                     waybar_content.clone()
-                }
-            } else {
-                waybar_content.clone()
-            };
+                });
 
             if let Ok(with_module) =
                 waybar_config::add_module(&with_module_config, &waybar_module_name, section)
@@ -297,30 +664,174 @@ async fn update_module_async(
         }
 
         handle_css_injection(&uuid, &install_path).await;
+
+        if let Err(e) = run_hook_async(&uuid, &install_path, LifecycleHook::Enable).await {
+            tracing::warn!("{e}");
+        }
     }
 
     tracing::info!("Updated module: {}", uuid);
-    Ok(updated)
+    Ok(UpdateOutcome::Updated(updated, resolved))
+}
+
+struct StagedRelease {
+    findings: Vec<ScriptFinding>,
+    permissions: PermissionSet,
+    signed_by: Option<String>,
+    author_key: AuthorKeyStatus,
+}
+
+async fn stage_release(
+    uuid: &str,
+    author: &str,
+    version: &ModuleVersion,
+    repo_url: &str,
+    checksum: Option<&str>,
+    staging_dir: &Path,
+) -> Result<StagedRelease, String> {
+    if staging_dir.exists() {
+        tokio::fs::remove_dir_all(staging_dir)
+            .await
+            .map_err(|e| format!("Failed to clear staging directory: {e}"))?;
+    }
+
+    let staged = match checksum {
+        Some(expected_hash) => install_secure(
+            uuid,
+            author,
+            &version.to_string(),
+            expected_hash,
+            staging_dir,
+        )
+        .await
+        .map(|(result, author_key)| StagedRelease {
+            findings: result.script_review,
+            permissions: result.permissions,
+            signed_by: Some(result.signer.to_string()),
+            author_key,
+        }),
+        None => stage_unsigned(author, repo_url, staging_dir).await,
+    };
+
+    if staged.is_err()
+        && let Err(e) = tokio::fs::remove_dir_all(staging_dir).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove staged files for {uuid}: {e}");
+    }
+    staged
+}
+
+async fn stage_unsigned(
+    author: &str,
+    repo_url: &str,
+    staging_dir: &Path,
+) -> Result<StagedRelease, String> {
+    tokio::fs::create_dir_all(staging_dir)
+        .await
+        .map_err(|e| format!("Failed to create staging directory: {e}"))?;
+
+    download_module_files(repo_url, staging_dir).await?;
+    make_scripts_executable(staging_dir).await?;
+
+    let module_dir = staging_dir.to_path_buf();
+    let (findings, permissions) = tokio::task::spawn_blocking(move || {
+        let package_toml_path = module_dir.join("Package.toml");
+        let permissions = if package_toml_path.exists() {
+            PackageToml::from_file(&package_toml_path)
+                .map_err(|e| e.to_string())?
+                .permission_set()
+        } else {
+            PermissionSet::default()
+        };
+        Ok::<_, String>((review_module_scripts(&module_dir), permissions))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {e}"))??;
+
+    let author_key = AuthorKeyPins::load()
+        .check(author, &[], None, None)
+        .map_err(|e| e.to_string())?;
+    Ok(StagedRelease {
+        findings,
+        permissions,
+        signed_by: None,
+        author_key,
+    })
+}
+
+pub fn load_update_diff(update: PendingUpdate) -> Task<Message> {
+    Task::perform(
+        async move {
+            let uuid = update.uuid.clone();
+            update_diff_async(update)
+                .await
+                .map(|diff| (uuid.clone(), diff))
+                .map_err(|e| (uuid, e))
+        },
+        Message::UpdateDiffLoaded,
+    )
+}
+
+pub async fn update_diff_async(update: PendingUpdate) -> Result<ModuleDiff, String> {
+    let uuid = update.uuid.clone();
+    let install_path = paths::module_install_path(&uuid);
+    if !install_path.is_dir() {
+        return Err(format!("Module not installed: {uuid}"));
+    }
+
+    let staging_dir = paths::module_diff_staging_dir(&uuid);
+    stage_release(
+        &update.uuid,
+        &update.author,
+        &update.version,
+        &update.repo_url,
+        update.checksum.as_deref(),
+        &staging_dir,
+    )
+    .await?;
+
+    let updated_dir = staging_dir.clone();
+    let diff =
+        tokio::task::spawn_blocking(move || ModuleDiff::compute(&install_path, &updated_dir))
+            .await
+            .map_err(|e| format!("Task failed: {e}"));
+
+    if let Err(e) = tokio::fs::remove_dir_all(&staging_dir).await {
+        tracing::warn!("Failed to remove staged diff for {uuid}: {e}");
+    }
+    diff
 }
 
 async fn update_all_modules_async(
-    updates: Vec<(String, String, ModuleVersion)>,
-) -> Result<usize, String> {
-    let mut success_count = 0;
-
-    for (uuid, repo_url, new_version) in updates {
-        match update_module_async(uuid.clone(), repo_url, new_version).await {
-            Ok(_) => {
-                success_count += 1;
+    updates: Vec<PendingUpdate>,
+) -> Result<BatchUpdateReport, String> {
+    let mut report = BatchUpdateReport::default();
+
+    for update in updates {
+        let uuid = update.uuid.clone();
+        match update_module_async(update, LocalChangesPolicy::Keep, None, None).await {
+            Ok(UpdateOutcome::Updated(module, resolved)) => {
+                report.updated += 1;
                 tracing::info!("Updated module: {}", uuid);
+                let kept: Vec<String> = resolved
+                    .into_iter()
+                    .filter(|file| file.resolution == Resolution::Kept)
+                    .map(|file| file.path)
+                    .collect();
+                if !kept.is_empty() {
+                    report.kept_local.push((module.waybar_module_name, kept));
+                }
             }
+            Ok(UpdateOutcome::Blocked(escalation)) => report.blocked.push(escalation),
+            Ok(UpdateOutcome::KeyChanged(change)) => report.key_changes.push(change),
             Err(e) => {
                 tracing::warn!("Failed to update module {}: {}", uuid, e);
             }
         }
     }
 
-    Ok(success_count)
+    Ok(report)
 }
 
 fn parse_github_url(repo_url: &str) -> Result<(String, String), String> {
@@ -383,50 +894,10 @@ async fn extract_tarball(response: reqwest::Response, install_path: &Path) -> Re
 }
 
 fn extract_tarball_sync(bytes: &[u8], install_path: &Path) -> Result<(), String> {
-    let cursor = Cursor::new(bytes);
-    let decoder = GzDecoder::new(cursor);
-    let mut archive = Archive::new(decoder);
-
-    let mut extracted_count = 0;
-
-    for entry in archive
-        .entries()
-        .map_err(|e| format!("Failed to read archive entries: {e}"))?
-    {
-        let mut entry = entry.map_err(|e| format!("Failed to read entry: {e}"))?;
-
-        let path = entry
-            .path()
-            .map_err(|e| format!("Failed to get entry path: {e}"))?
-            .into_owned();
-
-        let components: Vec<_> = path.components().collect();
-        if components.len() <= 1 {
-            continue;
-        }
-
-        let relative_path: PathBuf = components[1..].iter().collect();
-        let dest_path = validate_extraction_path(install_path, &relative_path)
-            .map_err(|e| format!("Path traversal blocked: {e}"))?;
-
-        if entry.header().entry_type().is_dir() {
-            std::fs::create_dir_all(&dest_path)
-                .map_err(|e| format!("Failed to create directory {}: {e}", dest_path.display()))?;
-        } else if entry.header().entry_type().is_file() {
-            if let Some(parent) = dest_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .map_err(|e| format!("Failed to create parent directory: {e}"))?;
-            }
-
-            let mut file = std::fs::File::create(&dest_path)
-                .map_err(|e| format!("Failed to create file {}: {e}", dest_path.display()))?;
-
-            std::io::copy(&mut entry, &mut file)
-                .map_err(|e| format!("Failed to write file {}: {e}", dest_path.display()))?;
-
-            extracted_count += 1;
-        }
-    }
+    let extracted_count = ArchiveExtractor::new()
+        .strip_first_component()
+        .extract(bytes, install_path)
+        .map_err(|e| format!("Failed to extract archive: {e}"))?;
 
     tracing::info!(
         "Extracted {} files to {}",
@@ -441,7 +912,7 @@ fn extract_tarball_sync(bytes: &[u8], install_path: &Path) -> Result<(), String>
     Ok(())
 }
 
-async fn toggle_module_async(uuid: String, enabled: bool) -> Result<String, (String, String)> {
+pub async fn toggle_module_async(uuid: String, enabled: bool) -> Result<String, (String, String)> {
     use crate::services::waybar_config;
 
     let state_path = paths::data_dir().join("installed.json");
@@ -466,6 +937,24 @@ async fn toggle_module_async(uuid: String, enabled: bool) -> Result<String, (Str
         .map(|p| p.section)
         .unwrap_or(BarSection::Center);
 
+    let waybar_content = waybar_config::load_config().await.ok();
+    let with_module_config = match &waybar_content {
+        Some(waybar_content) if enabled => Some(
+            merge_module_definition(&uuid, &install_path, waybar_content)
+                .await
+                .map_err(|e| (uuid.clone(), e))?,
+        ),
+        _ => None,
+    };
+
+    if enabled {
+        run_hook_async(&uuid, &install_path, LifecycleHook::Enable)
+            .await
+            .map_err(|e| (uuid.clone(), e))?;
+    } else if let Err(e) = run_hook_async(&uuid, &install_path, LifecycleHook::Disable).await {
+        tracing::warn!("{e}");
+    }
+
     module.enabled = enabled;
 
     let new_content = serde_json::to_string_pretty(&modules)
@@ -475,34 +964,8 @@ async fn toggle_module_async(uuid: String, enabled: bool) -> Result<String, (Str
         .await
         .map_err(|e| (uuid.clone(), format!("Failed to save state: {e}")))?;
 
-    if let Ok(waybar_content) = waybar_config::load_config().await {
-        let modified = if enabled {
-            let config_path = install_path.join("config.jsonc");
-            let with_module_config = if config_path.exists() {
-                if let Ok(module_config) = tokio::fs::read_to_string(&config_path).await {
-                    let prefs = crate::services::preferences::load_preferences(&uuid);
-                    tracing::debug!("Loaded {} preferences for {}", prefs.len(), uuid);
-                    let module_config =
-                        waybar_config::substitute_preferences(&module_config, &prefs);
-                    tracing::debug!("Substituted config: {}", module_config);
-                    let install_path_str = install_path.to_string_lossy();
-                    waybar_config::merge_module_config(
-                        &waybar_content,
-                        &module_config,
-                        &install_path_str,
-                    )
-                    .unwrap_or_else(|e| {
-                        tracing::warn!("Failed to merge module config: {e}");
-                        waybar_content.clone()
-                    })
-                } else {
-                    tracing::warn!("Failed to read module config from {:?}", config_path);
-                    waybar_content.clone()
-                }
-            } else {
-                tracing::debug!("No config.jsonc found at {:?}", config_path);
-                waybar_content.clone()
-            };
+    if let Some(waybar_content) = waybar_content {
+        let modified = if let Some(with_module_config) = with_module_config {
             waybar_config::add_module(&with_module_config, &waybar_module_name, section)
         } else {
             let without_config =
@@ -543,6 +1006,64 @@ async fn toggle_module_async(uuid: String, enabled: bool) -> Result<String, (Str
     Ok(uuid)
 }
 
+pub async fn disable_revoked_module_async(uuid: String) -> Result<String, (String, String)> {
+    use crate::services::waybar_config;
+
+    let state_path = paths::data_dir().join("installed.json");
+
+    let content = tokio::fs::read_to_string(&state_path)
+        .await
+        .map_err(|e| (uuid.clone(), format!("Failed to read state: {e}")))?;
+
+    let mut modules: Vec<InstalledModule> = serde_json::from_str(&content)
+        .map_err(|e| (uuid.clone(), format!("Failed to parse state: {e}")))?;
+
+    let module = modules
+        .iter_mut()
+        .find(|m| m.uuid.to_string() == uuid)
+        .ok_or_else(|| (uuid.clone(), format!("Module not found: {uuid}")))?;
+    module.enabled = false;
+    let waybar_module_name = module.waybar_module_name.clone();
+
+    let new_content = serde_json::to_string_pretty(&modules)
+        .map_err(|e| (uuid.clone(), format!("Failed to serialize: {e}")))?;
+
+    tokio::fs::write(&state_path, new_content)
+        .await
+        .map_err(|e| (uuid.clone(), format!("Failed to save state: {e}")))?;
+
+    handle_css_removal(&uuid).await;
+
+    let waybar_content = waybar_config::load_config()
+        .await
+        .map_err(|e| (uuid.clone(), format!("Failed to load waybar config: {e}")))?;
+    let without_config = waybar_config::remove_module_config(&waybar_content, &waybar_module_name)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to remove module config: {e}");
+            waybar_content.clone()
+        });
+    let new_waybar_content = waybar_config::remove_module(&without_config, &waybar_module_name)
+        .map_err(|e| {
+            (
+                uuid.clone(),
+                format!("Failed to remove module from waybar: {e}"),
+            )
+        })?;
+
+    if let Err(e) = waybar_config::backup_config().await {
+        tracing::warn!("Failed to backup waybar config: {e}");
+    }
+    waybar_config::save_config(&new_waybar_content)
+        .await
+        .map_err(|e| (uuid.clone(), format!("Failed to save waybar config: {e}")))?;
+    if let Err(e) = waybar_config::reload_waybar().await {
+        tracing::warn!("Failed to reload waybar: {e}");
+    }
+
+    tracing::info!("Revoked module {uuid} disabled");
+    Ok(uuid)
+}
+
 async fn change_module_position_async(
     uuid: String,
     new_section: BarSection,
@@ -608,27 +1129,79 @@ async fn change_module_position_async(
     Ok(uuid)
 }
 
-async fn uninstall_module_async(uuid: String) -> Result<String, (String, String)> {
+#[derive(Debug, Clone, Error)]
+pub enum UninstallError {
+    #[error("{0}")]
+    Script(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+pub async fn uninstall_module_async(
+    uuid: String,
+    force: bool,
+) -> Result<(String, Option<String>), (String, UninstallError)> {
     use crate::services::waybar_config;
 
     let state_path = paths::data_dir().join("installed.json");
     let install_path = paths::module_install_path(&uuid);
 
-    let content = tokio::fs::read_to_string(&state_path)
-        .await
-        .map_err(|e| (uuid.clone(), format!("Failed to read state: {e}")))?;
+    let content = tokio::fs::read_to_string(&state_path).await.map_err(|e| {
+        (
+            uuid.clone(),
+            UninstallError::Other(format!("Failed to read state: {e}")),
+        )
+    })?;
 
-    let mut modules: Vec<InstalledModule> = serde_json::from_str(&content)
-        .map_err(|e| (uuid.clone(), format!("Failed to parse state: {e}")))?;
+    let mut modules: Vec<InstalledModule> = serde_json::from_str(&content).map_err(|e| {
+        (
+            uuid.clone(),
+            UninstallError::Other(format!("Failed to parse state: {e}")),
+        )
+    })?;
 
     let module = modules
         .iter()
         .find(|m| m.uuid.to_string() == uuid)
-        .ok_or_else(|| (uuid.clone(), format!("Module not found: {uuid}")))?;
+        .ok_or_else(|| {
+            (
+                uuid.clone(),
+                UninstallError::Other(format!("Module not found: {uuid}")),
+            )
+        })?;
 
     let was_enabled = module.enabled;
     let waybar_module_name = module.waybar_module_name.clone();
 
+    let script_output = if force {
+        tracing::warn!("Skipping uninstall script for {} (forced)", uuid);
+        None
+    } else {
+        if was_enabled
+            && let Err(e) = run_hook_async(&uuid, &install_path, LifecycleHook::Disable).await
+        {
+            tracing::warn!("{e}");
+        }
+
+        let hook_uuid = uuid.clone();
+        let module_dir = install_path.clone();
+        let output =
+            tokio::task::spawn_blocking(move || run_uninstall_script(&hook_uuid, &module_dir))
+                .await
+                .map_err(|e| {
+                    (
+                        uuid.clone(),
+                        UninstallError::Script(format!("Task failed: {e}")),
+                    )
+                })?
+                .map_err(|e| (uuid.clone(), UninstallError::Script(e.to_string())))?
+                .map(|result| result.stdout);
+        if let Some(ref stdout) = output {
+            tracing::info!("Uninstall script output for {}:\n{}", uuid, stdout);
+        }
+        output
+    };
+
     if was_enabled {
         if let Ok(waybar_content) = waybar_config::load_config().await {
             let without_config =
@@ -661,20 +1234,115 @@ async fn uninstall_module_async(uuid: String) -> Result<String, (String, String)
     match tokio::fs::remove_dir_all(&install_path).await {
         Ok(()) => {}
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
-        Err(e) => return Err((uuid, format!("Failed to remove module files: {e}"))),
+        Err(e) => {
+            return Err((
+                uuid,
+                UninstallError::Other(format!("Failed to remove module files: {e}")),
+            ));
+        }
+    }
+
+    let manifest_path = paths::module_manifest_path(&uuid);
+    if let Err(e) = tokio::fs::remove_file(&manifest_path).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove {}: {e}", manifest_path.display());
+    }
+
+    let baseline_dir = paths::module_baseline_dir(&uuid);
+    if let Err(e) = tokio::fs::remove_dir_all(&baseline_dir).await
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("Failed to remove {}: {e}", baseline_dir.display());
     }
 
     modules.retain(|m| m.uuid.to_string() != uuid);
 
-    let new_content = serde_json::to_string_pretty(&modules)
-        .map_err(|e| (uuid.clone(), format!("Failed to serialize: {e}")))?;
+    let new_content = serde_json::to_string_pretty(&modules).map_err(|e| {
+        (
+            uuid.clone(),
+            UninstallError::Other(format!("Failed to serialize: {e}")),
+        )
+    })?;
 
     tokio::fs::write(&state_path, new_content)
         .await
-        .map_err(|e| (uuid.clone(), format!("Failed to save state: {e}")))?;
+        .map_err(|e| {
+            (
+                uuid.clone(),
+                UninstallError::Other(format!("Failed to save state: {e}")),
+            )
+        })?;
 
     tracing::info!("Uninstalled module {}", uuid);
-    Ok(uuid)
+    Ok((uuid, script_output))
+}
+
+async fn record_manifest_async(uuid: &str, install_path: &Path) {
+    let uuid = uuid.to_string();
+    let module_dir = install_path.to_path_buf();
+    match tokio::task::spawn_blocking(move || record_install_manifest(&uuid, &module_dir)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::warn!("Failed to record install manifest: {e}"),
+        Err(e) => tracing::warn!("Install manifest task failed: {e}"),
+    }
+}
+
+async fn merge_module_definition(
+    uuid: &str,
+    install_path: &Path,
+    waybar_content: &str,
+) -> Result<String, String> {
+    use crate::services::waybar_config;
+
+    let config_path = install_path.join("config.jsonc");
+    if !config_path.exists() {
+        tracing::debug!("No config.jsonc found at {:?}", config_path);
+        return Ok(waybar_content.to_string());
+    }
+    let Ok(module_config) = tokio::fs::read_to_string(&config_path).await else {
+        tracing::warn!("Failed to read module config from {:?}", config_path);
+        return Ok(waybar_content.to_string());
+    };
+
+    let prefs = crate::services::preferences::load_preferences(uuid);
+    let module_config = waybar_config::substitute_preferences(&module_config, &prefs);
+    let executable = runtime_sandbox_executable();
+    let sandbox = executable
+        .as_deref()
+        .map(|executable| RuntimeSandbox { executable, uuid });
+    waybar_config::merge_module_config(
+        waybar_content,
+        &module_config,
+        &install_path.to_string_lossy(),
+        sandbox,
+    )
+}
+
+fn runtime_sandbox_executable() -> Option<String> {
+    if !crate::services::load_settings().sandbox_runtime {
+        return None;
+    }
+    std::env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|e| tracing::warn!("Cannot sandbox module scripts: {e}"))
+        .ok()
+}
+
+async fn run_hook_async(
+    uuid: &str,
+    module_dir: &Path,
+    hook: LifecycleHook,
+) -> Result<Option<String>, String> {
+    let uuid_owned = uuid.to_string();
+    let module_dir = module_dir.to_path_buf();
+
+    let result = tokio::task::spawn_blocking(move || run_hook(&uuid_owned, &module_dir, hook))
+        .await
+        .map_err(|e| format!("Task failed: {e}"))?
+        .map_err(|e| format!("{} hook failed for {uuid}: {e}", hook.name()))?;
+
+    Ok(result.map(|r| r.stdout))
 }
 
 pub async fn make_scripts_executable(install_path: &Path) -> Result<(), String> {
@@ -10,7 +10,9 @@ use crate::api::{
 };
 use crate::app::Message;
 use crate::domain::{AuthorProfile, ModuleUuid, RegistryIndex, ReviewsResponse};
+use crate::security::Verifier;
 use crate::services::paths;
+use std::path::Path;
 
 pub fn load_registry() -> Task<Message> {
     Task::perform(fetch_registry_async(), Message::RegistryLoaded)
@@ -20,12 +22,8 @@ pub fn refresh_registry() -> Task<Message> {
     Task::perform(refresh_registry_async(), Message::RegistryRefreshed)
 }
 
-async fn fetch_registry_async() -> Result<RegistryIndex, String> {
-    let cache_path = paths::registry_cache_path();
-
-    if let Ok(content) = tokio::fs::read_to_string(&cache_path).await
-        && let Ok(index) = serde_json::from_str::<RegistryIndex>(&content)
-    {
+pub async fn fetch_registry_async() -> Result<RegistryIndex, String> {
+    if let Some(index) = load_cached_index().await {
         tracing::info!(
             "Loaded registry from cache ({} modules)",
             index.modules.len()
@@ -34,60 +32,130 @@ async fn fetch_registry_async() -> Result<RegistryIndex, String> {
     }
 
     tracing::info!("Fetching registry");
-    let config = registry_configuration();
-    let api_index = default_api::api_v1_index_get(&config)
+    let index = fetch_and_cache_index(&registry_configuration()).await?;
+    tracing::info!("Fetched {} modules from registry", index.modules.len());
+    Ok(index)
+}
+
+async fn refresh_registry_async() -> Result<RegistryIndex, String> {
+    tracing::info!("Force refreshing registry");
+    let index = fetch_and_cache_index(&registry_configuration()).await?;
+    tracing::info!("Refreshed registry: {} modules", index.modules.len());
+    Ok(index)
+}
+
+async fn load_cached_index() -> Option<RegistryIndex> {
+    let cache_path = paths::registry_cache_path();
+    let body = tokio::fs::read(&cache_path).await.ok()?;
+    let signature = tokio::fs::read_to_string(paths::signature_path(&cache_path))
         .await
-        .map_err(|e| format!("Network error: {e}"))?;
-    let index = map_registry_index(api_index).map_err(|e| format!("Invalid registry data: {e}"))?;
+        .ok()?;
+
+    match verify_index(&Verifier::new(), &body, &signature) {
+        Ok(index) => Some(index),
+        Err(e) => {
+            tracing::warn!("Ignoring registry cache: {e}");
+            None
+        }
+    }
+}
 
+async fn fetch_and_cache_index(config: &Configuration) -> Result<RegistryIndex, String> {
+    let (index, body, signature) = fetch_signed_index(config, &Verifier::new()).await?;
+    let version_path = paths::registry_version_path();
+    check_index_version(&version_path, index.version)?;
+
+    let cache_path = paths::registry_cache_path();
     if let Some(parent) = cache_path.parent()
         && let Err(e) = tokio::fs::create_dir_all(parent).await
     {
         tracing::warn!("Failed to create cache directory: {e}");
     }
-    if let Ok(content) = serde_json::to_string_pretty(&index)
-        && let Err(e) = tokio::fs::write(&cache_path, content).await
-    {
+    if let Err(e) = tokio::fs::write(paths::signature_path(&cache_path), &signature).await {
+        tracing::warn!("Failed to write registry signature cache: {e}");
+    } else if let Err(e) = tokio::fs::write(&cache_path, &body).await {
         tracing::warn!("Failed to write registry cache: {e}");
     }
+    if let Err(e) = record_index_version(&version_path, index.version) {
+        tracing::warn!("Failed to record registry version: {e}");
+    }
 
-    tracing::info!("Fetched {} modules from registry", index.modules.len());
     Ok(index)
 }
 
-async fn refresh_registry_async() -> Result<RegistryIndex, String> {
-    let cache_path = paths::registry_cache_path();
-    if let Err(e) = tokio::fs::remove_file(&cache_path).await {
-        tracing::debug!("Cache file removal skipped: {e}");
-    }
-
-    tracing::info!("Force refreshing registry");
-    let config = registry_configuration();
-    let api_index = default_api::api_v1_index_get(&config)
+async fn fetch_signed_index(
+    config: &Configuration,
+    verifier: &Verifier,
+) -> Result<(RegistryIndex, Vec<u8>, String), String> {
+    let index_url = format!("{}/api/v1/index", config.base_path);
+    let body = config
+        .client
+        .get(&index_url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| format!("Network error: {e}"))?
+        .bytes()
         .await
         .map_err(|e| format!("Network error: {e}"))?;
-    let index = map_registry_index(api_index).map_err(|e| format!("Invalid registry data: {e}"))?;
 
-    if let Some(parent) = cache_path.parent()
-        && let Err(e) = tokio::fs::create_dir_all(parent).await
-    {
-        tracing::warn!("Failed to create cache directory: {e}");
+    let response = config
+        .client
+        .get(format!("{index_url}.minisig"))
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {e}"))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err("Registry index is unsigned; refusing to use it".to_string());
     }
-    if let Ok(content) = serde_json::to_string_pretty(&index)
-        && let Err(e) = tokio::fs::write(&cache_path, content).await
-    {
-        tracing::warn!("Failed to write registry cache: {e}");
+    let signature = response
+        .error_for_status()
+        .map_err(|e| format!("Network error: {e}"))?
+        .text()
+        .await
+        .map_err(|e| format!("Network error: {e}"))?;
+
+    let index = verify_index(verifier, &body, &signature)?;
+    Ok((index, body.to_vec(), signature))
+}
+
+fn verify_index(
+    verifier: &Verifier,
+    body: &[u8],
+    signature: &str,
+) -> Result<RegistryIndex, String> {
+    verifier
+        .verify(body, signature)
+        .map_err(|e| format!("Registry index signature rejected: {e}"))?;
+    let api_index =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid registry data: {e}"))?;
+    map_registry_index(api_index).map_err(|e| format!("Invalid registry data: {e}"))
+}
+
+fn check_index_version(version_path: &Path, version: u32) -> Result<(), String> {
+    let seen = std::fs::read_to_string(version_path)
+        .ok()
+        .and_then(|content| content.trim().parse::<u32>().ok());
+    match seen {
+        Some(seen) if version < seen => Err(format!(
+            "Registry index version {version} is older than previously seen version {seen}"
+        )),
+        _ => Ok(()),
     }
+}
 
-    tracing::info!("Refreshed registry: {} modules", index.modules.len());
-    Ok(index)
+fn record_index_version(version_path: &Path, version: u32) -> std::io::Result<()> {
+    if let Some(parent) = version_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(version_path, version.to_string())
 }
 
 pub fn load_author_profile(username: String) -> Task<Message> {
     Task::perform(fetch_author_profile_async(username), Message::AuthorLoaded)
 }
 
-async fn fetch_author_profile_async(username: String) -> Result<AuthorProfile, String> {
+pub(crate) async fn fetch_author_profile_async(username: String) -> Result<AuthorProfile, String> {
     let config = registry_configuration();
     fetch_author_profile_with_config(&config, &username).await
 }
@@ -201,4 +269,61 @@ mod tests {
         assert_eq!(profile.author.username, "jane");
         assert!(profile.modules.is_empty());
     }
+
+    async fn index_server(signature: Option<&str>) -> (MockServer, Configuration) {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/index"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"version": 1, "modules": [], "categories": {}}"#),
+            )
+            .mount(&mock_server)
+            .await;
+        let signature_response = match signature {
+            Some(signature) => ResponseTemplate::new(200).set_body_string(signature),
+            None => ResponseTemplate::new(404),
+        };
+        Mock::given(method("GET"))
+            .and(path("/api/v1/index.minisig"))
+            .respond_with(signature_response)
+            .mount(&mock_server)
+            .await;
+
+        let config = Configuration {
+            base_path: mock_server.uri(),
+            client: (*HTTP_CLIENT).clone(),
+            ..Default::default()
+        };
+        (mock_server, config)
+    }
+
+    #[tokio::test]
+    async fn unsigned_index_is_refused() {
+        let (_server, config) = index_server(None).await;
+        let error = fetch_signed_index(&config, &Verifier::new())
+            .await
+            .unwrap_err();
+        assert!(error.contains("unsigned"));
+    }
+
+    #[tokio::test]
+    async fn index_with_bad_signature_is_refused() {
+        let (_server, config) = index_server(Some("untrusted comment: forged\nnot-base64\n")).await;
+        let error = fetch_signed_index(&config, &Verifier::new())
+            .await
+            .unwrap_err();
+        assert!(error.contains("signature rejected"));
+    }
+
+    #[test]
+    fn rolled_back_index_versions_are_refused() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let version_path = dir.path().join("registry_version");
+
+        assert!(check_index_version(&version_path, 3).is_ok());
+        record_index_version(&version_path, 5).unwrap();
+        assert!(check_index_version(&version_path, 5).is_ok());
+        assert!(check_index_version(&version_path, 4).is_err());
+    }
 }
@@ -0,0 +1,42 @@
+use iced::Task;
+
+use crate::app::Message;
+use crate::domain::InstalledModule;
+use crate::security::{RevokedModule, current_revocation_list};
+
+pub fn sweep_revocations(modules: &[InstalledModule], max_age_hours: u32) -> Task<Message> {
+    let installed: Vec<(String, String)> = modules
+        .iter()
+        .map(|m| (m.uuid.to_string(), m.version.to_string()))
+        .collect();
+    Task::perform(
+        audit_modules_async(installed, max_age_hours),
+        Message::RevocationSweepCompleted,
+    )
+}
+
+pub async fn audit_installed_async(
+    max_age_hours: u32,
+) -> Result<(usize, Vec<RevokedModule>), String> {
+    let modules = super::load_installed_async().await?;
+    let installed: Vec<(String, String)> = modules
+        .iter()
+        .map(|m| (m.uuid.to_string(), m.version.to_string()))
+        .collect();
+    let revoked = audit_modules_async(installed, max_age_hours).await?;
+    Ok((modules.len(), revoked))
+}
+
+async fn audit_modules_async(
+    installed: Vec<(String, String)>,
+    max_age_hours: u32,
+) -> Result<Vec<RevokedModule>, String> {
+    let list = current_revocation_list(max_age_hours)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(list.audit(
+        installed
+            .iter()
+            .map(|(uuid, version)| (uuid.as_str(), version.as_str())),
+    ))
+}
@@ -6,7 +6,8 @@ use crate::domain::{ModuleCategory, ModuleUuid, ModuleVersion, RegistryIndex, Re
 
 use crate::app::state::{
     App, AuthorProfileState, BrowseState, CategoryFilter, ConfirmationState, InstalledState,
-    LoadingState, ModuleDetailState, PreferencesState, Screen, SortField, SortOrder, ViewMode,
+    LoadingState, ModuleDetailState, PreferencesState, Screen, SortField, SortOrder,
+    UpdateDiffState, ViewMode,
 };
 use crate::domain::InstalledModule;
 use crate::theme::{AppTheme, ThemeMode};
@@ -73,6 +74,8 @@ impl InstalledModuleBuilder {
             installed_at: chrono::Utc::now(),
             registry_version: self.registry_version,
             position: None,
+            signed_by: None,
+            approved_permissions: None,
         }
     }
 }
@@ -191,12 +194,15 @@ pub fn test_app() -> App {
         omarchy_palette: None,
         module_detail: ModuleDetailState::default(),
         author_profile: AuthorProfileState::default(),
+        update_diff: UpdateDiffState::default(),
         confirmation: ConfirmationState::default(),
         preferences: PreferencesState::default(),
         spinner_frame: 0,
         last_spinner_update: Instant::now(),
         tray_enabled: false,
         tray_receiver: None,
+        sandbox_runtime: false,
+        revocation: crate::security::RevocationSettings::default(),
         sandbox_status: None,
     }
 }
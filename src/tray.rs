@@ -13,6 +13,7 @@ static TRAY_HANDLE: OnceLock<Arc<Mutex<Option<TrayHandle>>>> = OnceLock::new();
 #[derive(Debug, Clone)]
 pub enum TrayEvent {
     ShowWindow,
+    ShowInstalled,
     CheckUpdates,
     Quit,
 }
@@ -20,6 +21,7 @@ pub enum TrayEvent {
 pub struct BarforgeTray {
     event_sender: Sender<TrayEvent>,
     update_count: usize,
+    revoked_count: usize,
 }
 
 impl Tray for BarforgeTray {
@@ -30,11 +32,17 @@ impl Tray for BarforgeTray {
     }
 
     fn icon_name(&self) -> String {
-        "applications-other".into()
+        if self.revoked_count > 0 {
+            "dialog-warning".into()
+        } else {
+            "applications-other".into()
+        }
     }
 
     fn title(&self) -> String {
-        if self.update_count > 0 {
+        if self.revoked_count > 0 {
+            format!("Barforge ({} revoked modules)", self.revoked_count)
+        } else if self.update_count > 0 {
             format!("Barforge ({} updates)", self.update_count)
         } else {
             "Barforge".into()
@@ -72,12 +80,21 @@ impl Tray for BarforgeTray {
             ..Default::default()
         };
 
-        vec![
-            show_item.into(),
-            updates_item.into(),
-            MenuItem::Separator,
-            quit_item.into(),
-        ]
+        let mut items = vec![show_item.into()];
+        if self.revoked_count > 0 {
+            items.push(
+                StandardItem {
+                    label: format!("Revoked Modules Installed ({})", self.revoked_count),
+                    activate: Box::new(|tray: &mut Self| {
+                        let _ = tray.event_sender.send(TrayEvent::ShowInstalled);
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        items.extend([updates_item.into(), MenuItem::Separator, quit_item.into()]);
+        items
     }
 }
 
@@ -87,6 +104,7 @@ pub fn init() -> Option<Receiver<TrayEvent>> {
     let tray = BarforgeTray {
         event_sender: event_tx,
         update_count: 0,
+        revoked_count: 0,
     };
 
     thread::spawn(move || match tray.spawn() {
@@ -116,6 +134,17 @@ pub fn set_update_count(count: usize) {
     }
 }
 
+pub fn set_revoked_count(count: usize) {
+    if let Some(handle_store) = TRAY_HANDLE.get()
+        && let Ok(guard) = handle_store.lock()
+        && let Some(handle) = guard.as_ref()
+    {
+        handle.update(|tray| {
+            tray.revoked_count = count;
+        });
+    }
+}
+
 pub fn shutdown() {
     if let Some(handle_store) = TRAY_HANDLE.get()
         && let Ok(mut guard) = handle_store.lock()
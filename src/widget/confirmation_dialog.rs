@@ -2,49 +2,173 @@ use iced::widget::{Space, button, column, container, row, text};
 use iced::{Alignment, Element, Length};
 
 use crate::app::message::Message;
-use crate::app::state::ConfirmationAction;
+use crate::app::state::{
+    AuthorKeyChange, ConfirmationAction, PendingInstall, PendingUpdate, PermissionEscalation,
+};
+use crate::services::{LocalChangesPolicy, ORIG_SUFFIX, ScriptFinding};
 use crate::theme::{
     AppTheme, CONFIRMATION_DIALOG_WIDTH, FONT_MD, FONT_SM, SPACE_LG, SPACE_MD, SPACE_SM,
     button as button_style, container as cont_style,
 };
 
+fn install_review_message(pending: &PendingInstall) -> String {
+    let requested = pending.permissions.summary();
+    let mut message = if requested.is_empty() {
+        format!(
+            "\"{}\" v{} requests no special access.",
+            pending.name, pending.version
+        )
+    } else {
+        let mut message = format!("\"{}\" v{} requests:\n", pending.name, pending.version);
+        for line in requested {
+            message.push_str(&format!("\n  • {line}"));
+        }
+        message
+    };
+    if !pending.findings.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&script_review_message(&pending.name, &pending.findings));
+    }
+    message
+}
+
+fn script_review_message(name: &str, findings: &[ScriptFinding]) -> String {
+    let mut message = format!("\"{name}\" ships scripts with potentially risky behaviour:\n");
+    for finding in findings {
+        message.push_str(&format!("\n{}", finding.script));
+        for issue in &finding.issues {
+            message.push_str(&format!("\n  • {}:{issue}", finding.script));
+        }
+    }
+    message.push_str("\n\nOnly continue if you trust this module's author.");
+    message
+}
+
+fn escalation_message(escalation: &PermissionEscalation) -> String {
+    let mut message = format!(
+        "\"{}\" v{} asks for access you have not approved:\n",
+        escalation.name, escalation.version
+    );
+    for line in &escalation.added {
+        message.push_str(&format!("\n  • {line}"));
+    }
+    message.push_str("\n\nOnly approve if you trust this module's author.");
+    message
+}
+
+fn author_key_message(change: &AuthorKeyChange) -> String {
+    let warning = change.status.warning(&change.author).unwrap_or_default();
+    format!(
+        "\"{}\" v{}: {warning}\n\nOnly update if you have confirmed the new key with the author.",
+        change.name, change.version
+    )
+}
+
+fn local_changes_message(pending: &PendingUpdate) -> String {
+    let mut message = format!(
+        "You have edited files in \"{}\" that the update would replace:\n",
+        pending.name
+    );
+    for path in &pending.modified {
+        message.push_str(&format!("\n  • {path}"));
+    }
+    message.push_str(&format!(
+        "\n\nKeep your versions, overwrite them, or merge text files with the update. \
+         Replaced files are saved with a {ORIG_SUFFIX} suffix."
+    ));
+    message
+}
+
 pub fn confirmation_dialog(
     action: &ConfirmationAction,
     theme: &AppTheme,
 ) -> Element<'static, Message> {
-    let (title, message) = match action {
+    let author_warning = match action {
+        ConfirmationAction::ReviewInstall(pending) => pending.author_key.warning(&pending.author),
+        _ => None,
+    };
+
+    let (title, message, confirm_label) = match action {
         ConfirmationAction::UninstallModule { name, .. } => (
             "Uninstall Module",
             format!(
                 "Are you sure you want to uninstall \"{}\"? This action cannot be undone.",
                 name
             ),
+            "Uninstall",
+        ),
+        ConfirmationAction::ForceUninstallModule { name, error, .. } => (
+            "Uninstall Script Failed",
+            format!(
+                "{}\n\nRemove \"{}\" anyway? Its cleanup script will be skipped.",
+                error, name
+            ),
+            "Force Uninstall",
+        ),
+        ConfirmationAction::ReviewInstall(pending) => (
+            "Review Permissions",
+            install_review_message(pending),
+            "Approve & Install",
+        ),
+        ConfirmationAction::ApprovePermissions(escalation) => (
+            "New Permissions Requested",
+            escalation_message(escalation),
+            "Approve & Update",
+        ),
+        ConfirmationAction::ApproveAuthorKey(change) => (
+            "Author Key Warning",
+            author_key_message(change),
+            "Update Anyway",
+        ),
+        ConfirmationAction::ResolveLocalChanges(pending) => (
+            "Local Changes Detected",
+            local_changes_message(pending),
+            "Merge",
         ),
     };
 
+    let (title, confirm_label) = if author_warning.is_some() {
+        ("Author Key Warning", "Install Anyway")
+    } else {
+        (title, confirm_label)
+    };
+
     let cancel_btn = button(text("Cancel").size(FONT_SM))
         .on_press(Message::CancelConfirmation)
         .padding([SPACE_SM, SPACE_MD])
         .style(button_style::secondary(*theme));
 
-    let confirm_btn = button(text("Uninstall").size(FONT_SM))
+    let confirm_btn = button(text(confirm_label).size(FONT_SM))
         .on_press(Message::ConfirmAction)
         .padding([SPACE_SM, SPACE_MD])
         .style(button_style::danger(*theme));
 
-    let buttons = row![cancel_btn, confirm_btn]
+    let mut buttons = row![cancel_btn]
         .spacing(SPACE_SM)
         .align_y(Alignment::Center);
+    if matches!(action, ConfirmationAction::ResolveLocalChanges(_)) {
+        for (label, policy) in [
+            ("Keep Mine", LocalChangesPolicy::Keep),
+            ("Overwrite", LocalChangesPolicy::Overwrite),
+        ] {
+            buttons = buttons.push(
+                button(text(label).size(FONT_SM))
+                    .on_press(Message::ResolveLocalChanges(policy))
+                    .padding([SPACE_SM, SPACE_MD])
+                    .style(button_style::secondary(*theme)),
+            );
+        }
+    }
+    let buttons = buttons.push(confirm_btn);
 
-    let dialog_content = column![
-        text(title).size(FONT_MD).color(theme.text_normal),
-        text(message).size(FONT_SM).color(theme.text_muted),
-        Space::new().height(SPACE_SM),
-        buttons,
-    ]
-    .spacing(SPACE_SM)
-    .align_x(Alignment::Center)
-    .width(Length::Fixed(CONFIRMATION_DIALOG_WIDTH));
+    let dialog_content = column![text(title).size(FONT_MD).color(theme.text_normal)]
+        .push(author_warning.map(|warning| text(warning).size(FONT_SM).color(theme.danger)))
+        .push(text(message).size(FONT_SM).color(theme.text_muted))
+        .push(Space::new().height(SPACE_SM))
+        .push(buttons)
+        .spacing(SPACE_SM)
+        .align_x(Alignment::Center)
+        .width(Length::Fixed(CONFIRMATION_DIALOG_WIDTH));
 
     let dialog = container(dialog_content)
         .padding(SPACE_LG)
@@ -17,7 +17,7 @@ pub use confirmation_dialog::confirmation_dialog;
 pub use empty_state::{empty_state, empty_state_dynamic, empty_state_with_action};
 pub use module_card::module_card;
 pub use module_detail_screen::module_detail_screen;
-pub use module_row::module_row;
+pub use module_row::{ModuleFlags, module_row};
 pub use module_table::module_table;
 pub use notification::notification_toast;
 pub use preferences_modal::preferences_modal;
@@ -3,7 +3,7 @@ use iced::widget::{Space, button, column, container, image, row, scrollable, tex
 use iced::{Alignment, Background, Border, Element, Length};
 
 use crate::app::message::Message;
-use crate::app::state::{ReviewsLoadingState, ScreenshotState};
+use crate::app::state::{ModuleDetailState, ReviewsLoadingState, ScreenshotState};
 use crate::domain::{RegistryModule, Review};
 use crate::icons::Icon;
 use crate::theme::{
@@ -16,6 +16,8 @@ use crate::theme::{
 use super::category_style;
 use super::format_relative_time;
 
+const INSTALL_OUTPUT_VISIBLE_LINES: usize = 6;
+
 fn rating_stars_element<'a>(rating: f32, theme: &AppTheme) -> Element<'a, Message> {
     let full_stars = rating.floor() as usize;
     let has_half = rating - rating.floor() >= 0.5;
@@ -83,11 +85,9 @@ fn review_card<'a>(review: &'a Review, theme: &'a AppTheme) -> Element<'a, Messa
 
 pub fn module_detail_screen<'a>(
     module: &'a RegistryModule,
-    screenshot_state: &ScreenshotState,
-    reviews_state: &'a ReviewsLoadingState,
+    detail: &'a ModuleDetailState,
     is_installed: bool,
     installed_at: Option<DateTime<Utc>>,
-    installing: bool,
     theme: &'a AppTheme,
 ) -> Element<'a, Message> {
     let theme_copy = *theme;
@@ -210,10 +210,26 @@ pub fn module_detail_screen<'a>(
             ..Default::default()
         })
         .into()
-    } else if installing {
-        container(text("Installing...").size(FONT_SM).color(theme.text_muted))
-            .padding([SPACE_SM, SPACE_LG])
-            .into()
+    } else if detail.installing {
+        let start = detail
+            .install_output
+            .len()
+            .saturating_sub(INSTALL_OUTPUT_VISIBLE_LINES);
+        let output_lines = detail.install_output[start..].iter().map(|line| {
+            text(line)
+                .size(FONT_XS)
+                .font(iced::Font::MONOSPACE)
+                .color(theme.text_faint)
+                .into()
+        });
+
+        container(
+            column![text("Installing...").size(FONT_SM).color(theme.text_muted)]
+                .extend(output_lines)
+                .spacing(SPACE_XS),
+        )
+        .padding([SPACE_SM, SPACE_LG])
+        .into()
     } else {
         button(
             row![
@@ -229,7 +245,43 @@ pub fn module_detail_screen<'a>(
         .into()
     };
 
-    let screenshot_section: Element<Message> = match screenshot_state {
+    let permissions_section: Element<Message> = match &detail.requested_permissions {
+        Some(permissions) => {
+            let summary = permissions.summary();
+            let lines: Vec<Element<Message>> = if summary.is_empty() {
+                vec![
+                    text("No special access requested")
+                        .size(FONT_SM)
+                        .color(theme.text_muted)
+                        .into(),
+                ]
+            } else {
+                summary
+                    .into_iter()
+                    .map(|line| {
+                        text(format!("• {line}"))
+                            .size(FONT_SM)
+                            .color(theme.text_muted)
+                            .into()
+                    })
+                    .collect()
+            };
+            container(column![
+                text("Requested Access")
+                    .size(FONT_LG)
+                    .color(theme.text_normal),
+                Space::new().height(SPACE_SM),
+                column(lines).spacing(SPACE_XS),
+            ])
+            .style(cont_style::card(*theme))
+            .padding(SPACE_LG)
+            .width(Length::Fill)
+            .into()
+        }
+        None => Space::new().into(),
+    };
+
+    let screenshot_section: Element<Message> = match &detail.screenshot {
         ScreenshotState::Loading => container(
             column![
                 text("Loading screenshot...")
@@ -317,7 +369,7 @@ pub fn module_detail_screen<'a>(
     .padding(SPACE_LG)
     .width(Length::Fill);
 
-    let reviews_content: Element<'a, Message> = match reviews_state {
+    let reviews_content: Element<'a, Message> = match &detail.reviews {
         ReviewsLoadingState::Loading => container(
             text("Loading reviews...")
                 .size(FONT_SM)
@@ -466,6 +518,8 @@ pub fn module_detail_screen<'a>(
         hero_section,
         Space::new().height(SPACE_MD),
         install_section,
+        Space::new().height(SPACE_MD),
+        permissions_section,
         Space::new().height(SPACE_LG),
         screenshot_section,
         Space::new().height(SPACE_LG),
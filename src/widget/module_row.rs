@@ -4,18 +4,30 @@ use iced::{Alignment, Element, Length};
 use crate::app::message::Message;
 use crate::app::state::ConfirmationAction;
 use crate::domain::{BarSection, InstalledModule};
+use crate::security::RevokedModule;
+use crate::services::IntegrityReport;
 use crate::theme::{
     AppTheme, FONT_2XS, FONT_SM, FONT_XS, PickListColors, RADIUS_SM, SPACE_LG, SPACE_MD, SPACE_SM,
     button as btn_style, container as cont_style, menu_style, pick_list_style,
 };
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModuleFlags<'a> {
+    pub integrity: Option<&'a IntegrityReport>,
+    pub revoked: Option<&'a RevokedModule>,
+}
+
 pub fn module_row(
     module: &InstalledModule,
     is_toggling: bool,
     is_uninstalling: bool,
+    missing_deps: &[String],
+    is_rechecking: bool,
+    flags: ModuleFlags<'_>,
     theme: &AppTheme,
 ) -> Element<'static, Message> {
     let uuid = module.uuid.to_string();
+    let uuid_recheck = module.uuid.clone();
     let uuid_toggle = module.uuid.clone();
     let uuid_position = module.uuid.clone();
     let uuid_prefs = module.uuid.clone();
@@ -73,6 +85,45 @@ pub fn module_row(
         Space::new().width(0).into()
     };
 
+    let recheck_widget: Element<Message> = if is_rechecking {
+        container(text("Checking...").size(FONT_XS).color(theme.text_muted))
+            .padding([SPACE_SM, SPACE_MD])
+            .into()
+    } else if !missing_deps.is_empty() {
+        button(text("Re-check deps").size(FONT_XS))
+            .on_press(Message::RecheckDependencies(uuid_recheck))
+            .style(btn_style::secondary(*theme))
+            .padding([SPACE_SM, SPACE_MD])
+            .into()
+    } else {
+        Space::new().width(0).into()
+    };
+
+    let deps_text: Element<Message> = if missing_deps.is_empty() {
+        Space::new().width(0).into()
+    } else {
+        text(format!("Missing: {}", missing_deps.join(", ")))
+            .size(FONT_2XS)
+            .color(theme.warning)
+            .into()
+    };
+
+    let revoked_text: Element<Message> = match flags.revoked {
+        Some(revoked) => text(format!("Revoked: {}", revoked.reason))
+            .size(FONT_2XS)
+            .color(theme.danger)
+            .into(),
+        None => Space::new().width(0).into(),
+    };
+
+    let integrity_text: Element<Message> = match flags.integrity {
+        Some(report) if !report.is_clean() => text(format!("Modified: {report}"))
+            .size(FONT_2XS)
+            .color(theme.danger)
+            .into(),
+        _ => Space::new().width(0).into(),
+    };
+
     let uninstall_widget: Element<Message> = if is_uninstalling {
         container(text("Removing...").size(FONT_XS).color(theme.text_muted))
             .padding([SPACE_SM, SPACE_MD])
@@ -96,6 +147,9 @@ pub fn module_row(
             text(uuid).size(FONT_XS).color(theme.text_faint),
             Space::new().width(SPACE_MD),
             status_text,
+            deps_text,
+            revoked_text,
+            integrity_text,
         ]
         .spacing(SPACE_SM),
     ]
@@ -106,6 +160,7 @@ pub fn module_row(
             info_column,
             Space::new().width(Length::Fill),
             position_picker,
+            recheck_widget,
             prefs_widget,
             toggle_widget,
             uninstall_widget,
@@ -3,6 +3,7 @@ use iced::{Alignment, Element, Length};
 
 use crate::app::message::Message;
 use crate::icons::Icon;
+use crate::security::{OfflinePolicy, RevocationSettings};
 use crate::theme::{
     AppTheme, FONT_2XL, FONT_LG, FONT_MD, FONT_SM, FONT_XS, ICON_SM, SETTINGS_CONTENT_MAX_WIDTH,
     SPACE_LG, SPACE_MD, SPACE_SM, SPACE_XL, SPACE_XS, button as btn_style, checkbox as chk_style,
@@ -11,7 +12,12 @@ use crate::theme::{
 
 const GITHUB_URL: &str = "https://github.com/jtaw5649/barforge-app";
 
-pub fn settings_screen(theme: &AppTheme, tray_enabled: bool) -> Element<'_, Message> {
+pub fn settings_screen(
+    theme: &AppTheme,
+    tray_enabled: bool,
+    sandbox_runtime: bool,
+    revocation: RevocationSettings,
+) -> Element<'_, Message> {
     let header = text("Settings").size(FONT_2XL).color(theme.text_normal);
 
     let data_label = text("Data").size(FONT_LG).color(theme.text_normal);
@@ -123,10 +129,65 @@ pub fn settings_screen(theme: &AppTheme, tray_enabled: bool) -> Element<'_, Mess
     .padding(SPACE_LG)
     .width(Length::Fill);
 
-    let content = column![header, appearance_section, data_section, about_section]
-        .spacing(SPACE_XL)
-        .padding(SPACE_XL)
-        .max_width(SETTINGS_CONTENT_MAX_WIDTH);
+    let security_label = text("Security").size(FONT_LG).color(theme.text_normal);
+
+    let sandbox_checkbox = checkbox(sandbox_runtime)
+        .label("Sandbox module scripts at runtime")
+        .on_toggle(Message::ToggleRuntimeSandbox)
+        .style(chk_style::themed(*theme));
+
+    let sandbox_desc =
+        text("Run module exec and click scripts through barforge with their approved permissions")
+            .size(FONT_XS)
+            .color(theme.text_faint);
+
+    let allow_offline = revocation.offline_policy == OfflinePolicy::AllowOffline;
+    let offline_checkbox = checkbox(allow_offline)
+        .label("Allow installs without recent revocation data")
+        .on_toggle(Message::ToggleOfflineInstalls)
+        .style(chk_style::themed(*theme));
+
+    let offline_desc =
+        text("Install when the security server and cached revocation list are both unavailable")
+            .size(FONT_XS)
+            .color(theme.text_faint);
+
+    let auto_disable_checkbox = checkbox(revocation.auto_disable_revoked)
+        .label("Disable revoked modules automatically")
+        .on_toggle(Message::ToggleAutoDisableRevoked)
+        .style(chk_style::themed(*theme));
+
+    let auto_disable_desc =
+        text("Turn off installed modules as soon as their version appears on the revocation list")
+            .size(FONT_XS)
+            .color(theme.text_faint);
+
+    let security_section = container(
+        column![
+            security_label,
+            Space::new().height(SPACE_MD),
+            column![sandbox_checkbox, sandbox_desc].spacing(SPACE_XS),
+            Space::new().height(SPACE_SM),
+            column![offline_checkbox, offline_desc].spacing(SPACE_XS),
+            Space::new().height(SPACE_SM),
+            column![auto_disable_checkbox, auto_disable_desc].spacing(SPACE_XS),
+        ]
+        .spacing(SPACE_XS),
+    )
+    .style(cont_style::card(*theme))
+    .padding(SPACE_LG)
+    .width(Length::Fill);
+
+    let content = column![
+        header,
+        appearance_section,
+        security_section,
+        data_section,
+        about_section
+    ]
+    .spacing(SPACE_XL)
+    .padding(SPACE_XL)
+    .max_width(SETTINGS_CONTENT_MAX_WIDTH);
 
     scrollable(
         container(content)
@@ -6,3 +6,6 @@ mod registry_tests;
 
 #[path = "integration/module_lifecycle_tests.rs"]
 mod module_lifecycle_tests;
+
+#[path = "integration/sandbox_tests.rs"]
+mod sandbox_tests;
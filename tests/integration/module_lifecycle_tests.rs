@@ -149,3 +149,198 @@ fn test_registry_load_error_sets_failed_state() {
         other => panic!("Expected LoadingState::Failed, got {:?}", other),
     }
 }
+
+#[test]
+fn test_blocked_update_waits_for_permission_review() {
+    use barforge::app::handlers::{handle_confirm_action, handle_update_blocked};
+    use barforge::app::state::{ConfirmationAction, PermissionEscalation};
+    use barforge::domain::PermissionSet;
+    use barforge::testing::test_version;
+
+    let installed = vec![InstalledModuleBuilder::new("weather").build()];
+    let mut app = test_app_with_installed(installed);
+    app.installed.updating.insert("weather@test".to_string());
+
+    let requested = PermissionSet {
+        network: true,
+        ..Default::default()
+    };
+    let escalation = PermissionEscalation {
+        uuid: "weather@test".to_string(),
+        name: "custom/weather".to_string(),
+        version: test_version("2.0.0"),
+        requested: requested.clone(),
+        added: vec!["Network access".to_string()],
+    };
+    let _task = handle_update_blocked(&mut app, escalation.clone());
+
+    assert!(!app.installed.updating.contains("weather@test"));
+    assert!(app.installed.escalations.contains_key("weather@test"));
+
+    app.confirmation.pending_action = Some(ConfirmationAction::ApprovePermissions(escalation));
+    let _task = handle_confirm_action(&mut app);
+
+    assert!(app.installed.escalations.is_empty());
+    assert_eq!(
+        app.installed.approved_escalations.get("weather@test"),
+        Some(&requested)
+    );
+}
+
+#[test]
+fn test_update_with_different_author_key_waits_for_confirmation() {
+    use barforge::app::handlers::{handle_confirm_action, handle_update_key_changed};
+    use barforge::app::state::{AuthorKeyChange, ConfirmationAction};
+    use barforge::security::{AuthorKeyPin, AuthorKeyStatus};
+    use barforge::testing::test_version;
+
+    let installed = vec![InstalledModuleBuilder::new("weather").build()];
+    let mut app = test_app_with_installed(installed);
+    app.installed.updating.insert("weather@test".to_string());
+
+    let status = AuthorKeyStatus::Changed {
+        pinned: "0807060504030201".to_string(),
+        presented: AuthorKeyPin {
+            key_id: "1010101010101010".to_string(),
+            public_key: "RWQQEBAQEBAQEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string(),
+            pinned_at: chrono::Utc::now(),
+        },
+    };
+    let change = AuthorKeyChange {
+        uuid: "weather@test".to_string(),
+        name: "custom/weather".to_string(),
+        version: test_version("2.0.0"),
+        author: "jane".to_string(),
+        status: status.clone(),
+    };
+    let _task = handle_update_key_changed(&mut app, change.clone());
+
+    assert!(!app.installed.updating.contains("weather@test"));
+    assert!(app.installed.key_changes.contains_key("weather@test"));
+    assert!(status.needs_confirmation(app.installed.approved_key_changes.get("weather@test")));
+
+    app.confirmation.pending_action = Some(ConfirmationAction::ApproveAuthorKey(change));
+    let _task = handle_confirm_action(&mut app);
+
+    assert!(app.installed.key_changes.is_empty());
+    assert!(!status.needs_confirmation(app.installed.approved_key_changes.get("weather@test")));
+}
+
+#[test]
+fn test_missing_dependencies_raise_one_warning() {
+    use barforge::app::handlers::handle_dependencies_checked;
+    use barforge::services::DepReport;
+    use barforge::testing::test_uuid;
+    use std::collections::HashMap;
+
+    let installed = vec![
+        InstalledModuleBuilder::new("weather").build(),
+        InstalledModuleBuilder::new("clock").build(),
+    ];
+    let mut app = test_app_with_installed(installed);
+    let missing = |name: &str| DepReport {
+        all_satisfied: false,
+        missing_required: vec![name.to_string()],
+        results: HashMap::new(),
+    };
+
+    let _task = handle_dependencies_checked(
+        &mut app,
+        vec![
+            Ok((test_uuid("weather"), missing("curl"))),
+            Ok((test_uuid("clock"), missing("jq"))),
+        ],
+    );
+
+    assert_eq!(app.notifications.len(), 1);
+    let message = &app.notifications[0].message;
+    assert!(message.contains("curl") && message.contains("jq"));
+    assert_eq!(app.installed.dep_reports.len(), 2);
+}
+
+#[test]
+fn test_update_all_reports_kept_local_changes() {
+    use barforge::app::handlers::handle_update_all_completed;
+    use barforge::app::state::{BatchUpdateReport, NotificationKind};
+
+    let mut app = test_app();
+    app.installed.updating_all = true;
+
+    let _task = handle_update_all_completed(
+        &mut app,
+        Ok(BatchUpdateReport {
+            updated: 2,
+            kept_local: vec![("weather".to_string(), vec!["style.css".to_string()])],
+            ..BatchUpdateReport::default()
+        }),
+    );
+
+    assert!(!app.installed.updating_all);
+    let warning = app
+        .notifications
+        .iter()
+        .find(|n| n.kind == NotificationKind::Warning)
+        .expect("kept files are reported");
+    assert!(warning.message.contains("weather (style.css)"));
+}
+
+#[test]
+fn test_revoked_modules_marked_disabled_once_disables_complete() {
+    use barforge::app::handlers::handle_revoked_modules_disabled;
+
+    let installed = vec![
+        InstalledModuleBuilder::new("weather").enabled(true).build(),
+        InstalledModuleBuilder::new("clock").enabled(true).build(),
+    ];
+    let mut app = test_app_with_installed(installed);
+    app.installed.toggling.insert("weather@test".to_string());
+    app.installed.toggling.insert("clock@test".to_string());
+
+    let _task = handle_revoked_modules_disabled(
+        &mut app,
+        vec![
+            Ok("weather@test".to_string()),
+            Err((
+                "clock@test".to_string(),
+                "waybar config missing".to_string(),
+            )),
+        ],
+    );
+
+    assert!(app.installed.toggling.is_empty());
+    let enabled: Vec<bool> = app.installed_modules.iter().map(|m| m.enabled).collect();
+    assert_eq!(enabled, vec![false, true]);
+    assert_eq!(app.notifications.len(), 1);
+    assert!(app.notifications[0].message.contains("clock@test"));
+}
+
+#[test]
+fn test_force_uninstall_offered_only_for_script_failures() {
+    use barforge::app::handlers::handle_uninstall_completed;
+    use barforge::app::state::ConfirmationAction;
+    use barforge::tasks::UninstallError;
+
+    let installed = vec![InstalledModuleBuilder::new("weather").build()];
+    let mut app = test_app_with_installed(installed);
+
+    let _task = handle_uninstall_completed(
+        &mut app,
+        Err((
+            "weather@test".to_string(),
+            UninstallError::Other("Failed to parse state: EOF".to_string()),
+        )),
+    );
+    assert!(app.confirmation.pending_action.is_none());
+
+    let _task = handle_uninstall_completed(
+        &mut app,
+        Err((
+            "weather@test".to_string(),
+            UninstallError::Script("Uninstall hook failed".to_string()),
+        )),
+    );
+    assert!(matches!(
+        app.confirmation.pending_action,
+        Some(ConfirmationAction::ForceUninstallModule { .. })
+    ));
+}
@@ -0,0 +1,109 @@
+use barforge::security::{SandboxConfig, SandboxResult, apply_sandbox};
+use std::net::{TcpListener, TcpStream};
+
+fn in_sandbox<T: Send + 'static>(
+    config: SandboxConfig,
+    probe: impl FnOnce() -> T + Send + 'static,
+) -> (SandboxResult, T) {
+    std::thread::spawn(move || {
+        let result = apply_sandbox(&config);
+        (result, probe())
+    })
+    .join()
+    .expect("sandbox thread panicked")
+}
+
+fn network_supported() -> bool {
+    let (result, _) = in_sandbox(SandboxConfig::default(), || ());
+    if !result.network_isolated {
+        eprintln!("skipping: kernel does not enforce Landlock network rules");
+    }
+    result.network_isolated
+}
+
+#[test]
+fn test_sandbox_denies_connect_without_network_permission() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let config = SandboxConfig {
+        allow_network: false,
+        allowed_ports: vec![port],
+        ..Default::default()
+    };
+    let (result, connect) = in_sandbox(config, move || TcpStream::connect(("127.0.0.1", port)));
+
+    if result.network_isolated {
+        assert!(connect.is_err());
+    } else {
+        assert!(!network_supported());
+    }
+}
+
+#[test]
+fn test_sandbox_allows_only_declared_ports() {
+    let allowed = TcpListener::bind("127.0.0.1:0").unwrap();
+    let denied = TcpListener::bind("127.0.0.1:0").unwrap();
+    let allowed_port = allowed.local_addr().unwrap().port();
+    let denied_port = denied.local_addr().unwrap().port();
+
+    let config = SandboxConfig {
+        allow_network: true,
+        allowed_ports: vec![allowed_port],
+        ..Default::default()
+    };
+    let (result, (ok, blocked)) = in_sandbox(config, move || {
+        (
+            TcpStream::connect(("127.0.0.1", allowed_port)),
+            TcpStream::connect(("127.0.0.1", denied_port)),
+        )
+    });
+
+    assert!(!result.network_isolated);
+    assert!(ok.is_ok());
+    if network_supported() {
+        assert!(blocked.is_err());
+    }
+}
+
+#[test]
+fn test_sandbox_denies_bind() {
+    let config = SandboxConfig {
+        allow_network: true,
+        allowed_ports: vec![443],
+        ..Default::default()
+    };
+    let (_, bind) = in_sandbox(config, || TcpListener::bind("127.0.0.1:0"));
+
+    if network_supported() {
+        assert!(bind.is_err());
+    }
+}
+
+#[test]
+fn test_sandbox_module_dir_read_only_and_data_dir_writable() {
+    let root = tempfile::tempdir_in(env!("CARGO_TARGET_TMPDIR")).unwrap();
+    let module_dir = root.path().join("module");
+    let data_dir = root.path().join("data");
+    std::fs::create_dir_all(&module_dir).unwrap();
+    std::fs::write(module_dir.join("run.sh"), "echo ok").unwrap();
+
+    let config = SandboxConfig::default().for_module(&module_dir, &data_dir);
+    let (result, (read, module_write, data_write)) = in_sandbox(config, {
+        let module_dir = module_dir.clone();
+        let data_dir = data_dir.clone();
+        move || {
+            (
+                std::fs::read_to_string(module_dir.join("run.sh")),
+                std::fs::write(module_dir.join("dropped"), "x"),
+                std::fs::write(data_dir.join("state"), "x"),
+            )
+        }
+    });
+
+    assert_eq!(read.unwrap(), "echo ok");
+    assert!(data_write.is_ok());
+    if result.status.is_secure() {
+        assert!(module_write.is_err());
+    }
+}